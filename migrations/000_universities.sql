@@ -0,0 +1,47 @@
+-- universities used to be created by dataset/migrate.py, which has to happen
+-- before any migration that alters a table referencing it
+CREATE TABLE IF NOT EXISTS universities (
+	id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+	name VARCHAR NOT NULL,
+	aliases VARCHAR,
+	street_address VARCHAR NOT NULL,
+	city VARCHAR NOT NULL,
+	state VARCHAR NOT NULL,
+	zip_code VARCHAR NOT NULL,
+	website VARCHAR NOT NULL,
+	admissions_website VARCHAR,
+	longitude REAL NOT NULL,
+	latitude REAL NOT NULL,
+	total_enrollment INTEGER,
+	undergrad_enrollment INTEGER,
+	student_to_faculty INT8,
+	graduation_rate INT8,
+	open_admission BOOLEAN,
+	considers_gpa INT4 NOT NULL,
+	considers_class_rank INT4 NOT NULL,
+	considers_transcript INT4 NOT NULL,
+	considers_recommendations INT4 NOT NULL,
+	considers_test_scores INT4 NOT NULL,
+	considers_toefl INT4 NOT NULL,
+	total_applicants INTEGER,
+	total_admissions INTEGER,
+	total_enrolled_applicants INTEGER,
+	admissions_yield INTEGER,
+	submitted_sat INTEGER,
+	submitted_act INTEGER,
+	sat_english_1q INTEGER,
+	sat_english_3q INTEGER,
+	sat_math_1q INTEGER,
+	sat_math_3q INTEGER,
+	act_composite_1q INTEGER,
+	act_composite_3q INTEGER,
+	act_english_1q INTEGER,
+	act_english_3q INTEGER,
+	act_math_1q INTEGER,
+	act_math_3q INTEGER,
+	application_fee INT8,
+	price_in_district INTEGER,
+	price_in_state INTEGER,
+	price_out_of_state INTEGER,
+	timezone TEXT NOT NULL DEFAULT 'America/Los_Angeles'
+);
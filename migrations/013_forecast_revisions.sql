@@ -0,0 +1,37 @@
+-- keep every forecast issued instead of overwriting it on refresh
+CREATE TABLE forecast_revisions (
+	university_id INTEGER NOT NULL REFERENCES universities(id) ON DELETE CASCADE,
+	time DATETIME NOT NULL,
+	fetched_at DATETIME NOT NULL,
+	temperature REAL NOT NULL,
+	feels_like REAL NOT NULL,
+	weather_id INTEGER NOT NULL,
+	weather_description TEXT NOT NULL,
+	humidity REAL NOT NULL,
+	pressure REAL NOT NULL,
+	wind_speed REAL NOT NULL,
+	precipitation_chance REAL NOT NULL,
+	is_day BOOLEAN NOT NULL DEFAULT TRUE,
+	PRIMARY KEY (university_id, time, fetched_at)
+);
+
+INSERT INTO forecast_revisions SELECT
+	university_id,
+	time,
+	fetched_at,
+	temperature,
+	feels_like,
+	weather_id,
+	weather_description,
+	humidity,
+	pressure,
+	wind_speed,
+	precipitation_chance,
+	is_day
+FROM forecasts;
+
+DROP TABLE forecasts;
+
+ALTER TABLE forecast_revisions RENAME TO forecasts;
+
+CREATE INDEX forecasts_fetched_at ON forecasts (university_id, fetched_at);
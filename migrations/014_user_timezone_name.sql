@@ -0,0 +1,4 @@
+-- timezone preference is an IANA name (e.g. America/New_York) instead of an offset,
+-- which was never set by any client
+ALTER TABLE users DROP COLUMN timezone;
+ALTER TABLE users ADD COLUMN timezone TEXT;
@@ -0,0 +1,4 @@
+-- IPEDS unit id, so imports can update universities in place
+ALTER TABLE universities ADD COLUMN unitid INTEGER;
+
+CREATE UNIQUE INDEX universities_unitid ON universities (unitid);
@@ -0,0 +1,87 @@
+-- statistics that IPEDS reports every year, so they can be compared over time
+CREATE TABLE university_stats (
+	university_id INTEGER NOT NULL REFERENCES universities(id) ON DELETE CASCADE,
+	-- the fall the statistics are from (e.g., 2020 for 2020-21)
+	year INTEGER NOT NULL,
+	total_enrollment INTEGER,
+	undergrad_enrollment INTEGER,
+	student_to_faculty INTEGER,
+	graduation_rate INTEGER,
+	total_applicants INTEGER,
+	total_admissions INTEGER,
+	total_enrolled_applicants INTEGER,
+	admissions_yield INTEGER,
+	submitted_sat INTEGER,
+	submitted_act INTEGER,
+	sat_english_1q INTEGER,
+	sat_english_3q INTEGER,
+	sat_math_1q INTEGER,
+	sat_math_3q INTEGER,
+	act_composite_1q INTEGER,
+	act_composite_3q INTEGER,
+	act_english_1q INTEGER,
+	act_english_3q INTEGER,
+	act_math_1q INTEGER,
+	act_math_3q INTEGER,
+	application_fee INTEGER,
+	price_in_district INTEGER,
+	price_in_state INTEGER,
+	price_out_of_state INTEGER,
+	PRIMARY KEY (university_id, year)
+);
+
+-- every university so far came from the Fall 2020 data
+INSERT INTO university_stats
+SELECT
+	id,
+	2020,
+	total_enrollment,
+	undergrad_enrollment,
+	student_to_faculty,
+	graduation_rate,
+	total_applicants,
+	total_admissions,
+	total_enrolled_applicants,
+	admissions_yield,
+	submitted_sat,
+	submitted_act,
+	sat_english_1q,
+	sat_english_3q,
+	sat_math_1q,
+	sat_math_3q,
+	act_composite_1q,
+	act_composite_3q,
+	act_english_1q,
+	act_english_3q,
+	act_math_1q,
+	act_math_3q,
+	application_fee,
+	price_in_district,
+	price_in_state,
+	price_out_of_state
+FROM universities;
+
+ALTER TABLE universities DROP COLUMN total_enrollment;
+ALTER TABLE universities DROP COLUMN undergrad_enrollment;
+ALTER TABLE universities DROP COLUMN student_to_faculty;
+ALTER TABLE universities DROP COLUMN graduation_rate;
+ALTER TABLE universities DROP COLUMN total_applicants;
+ALTER TABLE universities DROP COLUMN total_admissions;
+ALTER TABLE universities DROP COLUMN total_enrolled_applicants;
+ALTER TABLE universities DROP COLUMN admissions_yield;
+ALTER TABLE universities DROP COLUMN submitted_sat;
+ALTER TABLE universities DROP COLUMN submitted_act;
+ALTER TABLE universities DROP COLUMN sat_english_1q;
+ALTER TABLE universities DROP COLUMN sat_english_3q;
+ALTER TABLE universities DROP COLUMN sat_math_1q;
+ALTER TABLE universities DROP COLUMN sat_math_3q;
+ALTER TABLE universities DROP COLUMN act_composite_1q;
+ALTER TABLE universities DROP COLUMN act_composite_3q;
+ALTER TABLE universities DROP COLUMN act_english_1q;
+ALTER TABLE universities DROP COLUMN act_english_3q;
+ALTER TABLE universities DROP COLUMN act_math_1q;
+ALTER TABLE universities DROP COLUMN act_math_3q;
+ALTER TABLE universities DROP COLUMN application_fee;
+ALTER TABLE universities DROP COLUMN price_in_district;
+ALTER TABLE universities DROP COLUMN price_in_state;
+ALTER TABLE universities DROP COLUMN price_out_of_state;
@@ -0,0 +1,5 @@
+-- Office of Postsecondary Education id (8 digits, with leading zeros), used by
+-- federal datasets that don't have the IPEDS unit id
+ALTER TABLE universities ADD COLUMN opeid TEXT;
+
+CREATE INDEX universities_opeid ON universities (opeid);
@@ -0,0 +1,16 @@
+-- outcomes from the College Scorecard (https://collegescorecard.ed.gov/data/)
+CREATE TABLE university_outcomes (
+	university_id INTEGER NOT NULL PRIMARY KEY REFERENCES universities(id) ON DELETE CASCADE,
+	-- median earnings of students working and not enrolled 10 years after entry
+	median_earnings INTEGER,
+	-- median debt of students who completed
+	median_debt REAL,
+	-- average net price by family income, for students receiving title IV aid
+	net_price_0_30000 INTEGER,
+	net_price_30001_48000 INTEGER,
+	net_price_48001_75000 INTEGER,
+	net_price_75001_110000 INTEGER,
+	net_price_110001_plus INTEGER,
+	-- fraction of first-time, full-time students who completed within 150% of normal time
+	completion_rate REAL
+);
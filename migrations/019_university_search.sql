@@ -0,0 +1,53 @@
+-- initials of the name (e.g., MIT), filled in by clime since sqlite can't compute it
+ALTER TABLE universities ADD COLUMN acronym TEXT;
+
+-- ranked full-text search
+CREATE VIRTUAL TABLE universities_search USING fts5 (
+	name,
+	aliases,
+	city,
+	state,
+	acronym,
+	content = 'universities',
+	content_rowid = 'id',
+	tokenize = 'unicode61 remove_diacritics 2'
+);
+
+-- substrings of 3 characters, to find names with typos
+CREATE VIRTUAL TABLE universities_fuzzy USING fts5 (
+	name,
+	aliases,
+	content = 'universities',
+	content_rowid = 'id',
+	tokenize = 'trigram'
+);
+
+INSERT INTO universities_search (universities_search) VALUES ('rebuild');
+INSERT INTO universities_fuzzy (universities_fuzzy) VALUES ('rebuild');
+
+-- keep the external content indices up to date
+CREATE TRIGGER universities_search_insert AFTER INSERT ON universities BEGIN
+	INSERT INTO universities_search (rowid, name, aliases, city, state, acronym)
+		VALUES (new.id, new.name, new.aliases, new.city, new.state, new.acronym);
+	INSERT INTO universities_fuzzy (rowid, name, aliases)
+		VALUES (new.id, new.name, new.aliases);
+END;
+
+CREATE TRIGGER universities_search_delete AFTER DELETE ON universities BEGIN
+	INSERT INTO universities_search (universities_search, rowid, name, aliases, city, state, acronym)
+		VALUES ('delete', old.id, old.name, old.aliases, old.city, old.state, old.acronym);
+	INSERT INTO universities_fuzzy (universities_fuzzy, rowid, name, aliases)
+		VALUES ('delete', old.id, old.name, old.aliases);
+END;
+
+CREATE TRIGGER universities_search_update
+AFTER UPDATE OF name, aliases, city, state, acronym ON universities BEGIN
+	INSERT INTO universities_search (universities_search, rowid, name, aliases, city, state, acronym)
+		VALUES ('delete', old.id, old.name, old.aliases, old.city, old.state, old.acronym);
+	INSERT INTO universities_search (rowid, name, aliases, city, state, acronym)
+		VALUES (new.id, new.name, new.aliases, new.city, new.state, new.acronym);
+	INSERT INTO universities_fuzzy (universities_fuzzy, rowid, name, aliases)
+		VALUES ('delete', old.id, old.name, old.aliases);
+	INSERT INTO universities_fuzzy (rowid, name, aliases)
+		VALUES (new.id, new.name, new.aliases);
+END;
@@ -0,0 +1,2 @@
+-- for finding universities within a bounding box
+CREATE INDEX universities_location ON universities (latitude, longitude);
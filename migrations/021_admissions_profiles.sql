@@ -0,0 +1,11 @@
+-- a user's own grades and test scores, to compare against each university
+CREATE TABLE admissions_profiles (
+	user_id BLOB NOT NULL PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+	-- unweighted, out of 4.0
+	gpa REAL,
+	sat_english INTEGER,
+	sat_math INTEGER,
+	act_composite INTEGER,
+	act_english INTEGER,
+	act_math INTEGER
+);
@@ -0,0 +1,18 @@
+-- application deadlines a user is keeping track of
+CREATE TABLE deadlines (
+	id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+	user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+	university_id INTEGER NOT NULL REFERENCES universities(id) ON DELETE CASCADE,
+	-- early_action, early_decision, regular, financial_aid or custom
+	kind TEXT NOT NULL,
+	-- required for custom deadlines
+	name TEXT,
+	-- due by the end of this day, in the university's timezone
+	due DATE NOT NULL,
+	notes TEXT,
+	-- how many reminders have been sent (one a week before, then one a day before)
+	reminders_sent INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE INDEX deadlines_user ON deadlines (user_id, university_id);
+CREATE INDEX deadlines_due ON deadlines (due);
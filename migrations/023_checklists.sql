@@ -0,0 +1,11 @@
+-- what a user still has to do to apply to each tracked university
+CREATE TABLE checklist_items (
+	id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+	user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+	university_id INTEGER NOT NULL REFERENCES universities(id) ON DELETE CASCADE,
+	-- the admission requirement an item was generated from, or NULL if the user added it
+	requirement TEXT,
+	label TEXT NOT NULL,
+	done BOOLEAN NOT NULL DEFAULT FALSE,
+	UNIQUE (user_id, university_id, requirement)
+);
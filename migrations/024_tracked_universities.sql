@@ -0,0 +1,24 @@
+-- what a user thinks of each university they're tracking
+ALTER TABLE get_weather ADD COLUMN notes TEXT;
+-- 1 to 5 stars
+ALTER TABLE get_weather ADD COLUMN rating INTEGER CHECK (rating BETWEEN 1 AND 5);
+-- reach, match, safety or visited
+ALTER TABLE get_weather ADD COLUMN category TEXT;
+-- where the user has sorted it, lowest first
+ALTER TABLE get_weather ADD COLUMN position INTEGER NOT NULL DEFAULT 0;
+
+-- keep the order universities were listed in before
+UPDATE get_weather SET position = (
+	SELECT COUNT(*) FROM get_weather AS g
+	WHERE g.user_id = get_weather.user_id AND g.university_id < get_weather.university_id
+);
+
+CREATE TABLE tracked_tags (
+	university_id INTEGER NOT NULL,
+	user_id BLOB NOT NULL,
+	tag TEXT NOT NULL,
+	PRIMARY KEY (university_id, user_id, tag),
+	FOREIGN KEY (university_id, user_id) REFERENCES get_weather (university_id, user_id) ON DELETE CASCADE
+);
+
+CREATE INDEX tracked_tags_user ON tracked_tags (user_id, tag);
@@ -0,0 +1,25 @@
+-- named lists of universities, like "Applying" or "Visit this fall"
+CREATE TABLE lists (
+	id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+	user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+	name TEXT NOT NULL,
+	-- every user has one, which mirrors the universities they're tracking
+	is_default BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX lists_user ON lists (user_id);
+CREATE UNIQUE INDEX lists_default ON lists (user_id) WHERE is_default;
+
+CREATE TABLE list_entries (
+	list_id INTEGER NOT NULL REFERENCES lists(id) ON DELETE CASCADE,
+	university_id INTEGER NOT NULL REFERENCES universities(id) ON DELETE CASCADE,
+	-- lowest first
+	position INTEGER NOT NULL DEFAULT 0,
+	PRIMARY KEY (list_id, university_id)
+);
+
+INSERT INTO lists (user_id, name, is_default) SELECT id, 'My universities', TRUE FROM users;
+
+INSERT INTO list_entries (list_id, university_id, position)
+SELECT lists.id, get_weather.university_id, get_weather.position
+FROM get_weather INNER JOIN lists ON lists.user_id = get_weather.user_id AND lists.is_default;
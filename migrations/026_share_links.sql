@@ -0,0 +1,12 @@
+-- unguessable links to view a list without an account
+CREATE TABLE share_links (
+	token TEXT NOT NULL PRIMARY KEY,
+	list_id INTEGER NOT NULL REFERENCES lists(id) ON DELETE CASCADE,
+	-- naive UTC, like every other timestamp
+	created_at TIMESTAMP NOT NULL,
+	-- NULL if it never expires
+	expires_at TIMESTAMP,
+	revoked BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX share_links_list ON share_links (list_id);
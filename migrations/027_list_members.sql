@@ -0,0 +1,25 @@
+-- everyone who can see or edit a list, including whoever made it
+CREATE TABLE list_members (
+	list_id INTEGER NOT NULL REFERENCES lists(id) ON DELETE CASCADE,
+	user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+	-- owner, editor or viewer
+	role TEXT NOT NULL,
+	-- FALSE until an invited user accepts
+	accepted BOOLEAN NOT NULL DEFAULT FALSE,
+	PRIMARY KEY (list_id, user_id)
+);
+
+CREATE INDEX list_members_user ON list_members (user_id);
+
+INSERT INTO list_members (list_id, user_id, role, accepted)
+SELECT id, user_id, 'owner', TRUE FROM lists;
+
+-- each member's own notes on the universities in a list
+CREATE TABLE list_notes (
+	list_id INTEGER NOT NULL,
+	university_id INTEGER NOT NULL,
+	user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+	notes TEXT NOT NULL,
+	PRIMARY KEY (list_id, university_id, user_id),
+	FOREIGN KEY (list_id, university_id) REFERENCES list_entries (list_id, university_id) ON DELETE CASCADE
+);
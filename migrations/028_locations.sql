@@ -0,0 +1,45 @@
+-- places a user wants the weather for that aren't universities (e.g., their home town)
+CREATE TABLE locations (
+	id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+	user_id BLOB NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+	name TEXT NOT NULL,
+	-- what the coordinates are called, according to OpenWeather
+	geocoded_name TEXT,
+	latitude REAL NOT NULL,
+	longitude REAL NOT NULL,
+	timezone TEXT NOT NULL
+);
+
+CREATE INDEX locations_user ON locations (user_id);
+
+-- like `weather`, for locations
+CREATE TABLE location_weather (
+	location_id INTEGER NOT NULL REFERENCES locations(id) ON DELETE CASCADE,
+	time DATETIME NOT NULL,
+	temperature REAL NOT NULL,
+	feels_like REAL NOT NULL,
+	weather_type INTEGER NOT NULL,
+	weather_description VARCHAR NOT NULL,
+	humidity REAL NOT NULL,
+	pressure REAL NOT NULL,
+	wind_speed REAL NOT NULL,
+	cloudiness REAL NOT NULL,
+	PRIMARY KEY (location_id, time)
+);
+
+-- like `forecasts`, but only the latest revision of each forecast
+CREATE TABLE location_forecasts (
+	location_id INTEGER NOT NULL REFERENCES locations(id) ON DELETE CASCADE,
+	time DATETIME NOT NULL,
+	fetched_at DATETIME NOT NULL,
+	temperature REAL NOT NULL,
+	feels_like REAL NOT NULL,
+	weather_id INTEGER NOT NULL,
+	weather_description TEXT NOT NULL,
+	humidity REAL NOT NULL,
+	pressure REAL NOT NULL,
+	wind_speed REAL NOT NULL,
+	precipitation_chance REAL NOT NULL,
+	is_day BOOLEAN NOT NULL,
+	PRIMARY KEY (location_id, time)
+);
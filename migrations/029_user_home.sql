@@ -0,0 +1,5 @@
+-- where a user lives, to compare each university against.
+-- the coordinates are looked up from the zip code if there is one
+ALTER TABLE users ADD COLUMN home_latitude REAL;
+ALTER TABLE users ADD COLUMN home_longitude REAL;
+ALTER TABLE users ADD COLUMN home_zip TEXT;
@@ -0,0 +1,5 @@
+-- a user's home is kept as a location (hidden from their list of locations),
+-- so its weather is saved and updated like any other location's
+ALTER TABLE locations ADD COLUMN is_home BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE UNIQUE INDEX locations_home ON locations (user_id) WHERE is_home;
@@ -79,6 +79,6 @@ pub struct ForecastResponse {
 
 impl Client {
 	api_route! {
-		GET "/forecast" pub forecast(#[to_string] cnt: u16, coordinates: Coordinates) -> ForecastResponse;
+		GET "/data/2.5/forecast" pub forecast(#[to_string] cnt: u16, coordinates: Coordinates) -> ForecastResponse;
 	}
 }
@@ -0,0 +1,45 @@
+use serde::Deserialize;
+
+use crate::{api_route, Client, Coordinates, GET};
+
+#[derive(Debug, Deserialize)]
+pub struct ZipResponse {
+	pub zip: String,
+	/// name of the area
+	pub name: String,
+	#[serde(flatten)]
+	pub coordinates: Coordinates,
+	/// ISO 3166 country code
+	pub country: String,
+}
+
+impl Client {
+	api_route! {
+		/// Find the location of a zip/post code, formatted as `{zip code},{country code}` (e.g., `90210,US`).
+		/// Returns `Error::NotFound` if there is no such zip code.
+		GET "/geo/1.0/zip" pub zip(#[to_string] zip: String) -> ZipResponse;
+	}
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Place {
+	/// name of the area
+	pub name: String,
+	#[serde(flatten)]
+	pub coordinates: Coordinates,
+	/// ISO 3166 country code
+	pub country: String,
+	/// only in some countries
+	pub state: Option<String>,
+}
+
+/// closest first
+pub type Places = Vec<Place>;
+
+impl Client {
+	api_route! {
+		/// Find the names of places near some coordinates, up to `limit` of them.
+		/// Returns an empty list if there aren't any (e.g., in the middle of the ocean).
+		GET "/geo/1.0/reverse" pub reverse(#[to_string] limit: u8, coordinates: Coordinates) -> Places;
+	}
+}
@@ -5,6 +5,7 @@ use reqwest::{header::HeaderValue, Method, RequestBuilder, StatusCode};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 pub mod forecast;
+pub mod geocoding;
 mod options;
 pub mod units;
 pub mod weather;
@@ -12,7 +13,7 @@ pub mod weather;
 pub use options::{Language, Options};
 pub use units::Units;
 
-pub(crate) const API_ENDPOINT: &str = "https://api.openweathermap.org/data/2.5";
+pub(crate) const API_ENDPOINT: &str = "https://api.openweathermap.org";
 
 static USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
@@ -22,6 +23,8 @@ pub enum Error {
 	Request(reqwest::Error),
 	#[error("Too many requests (ran out of API quota)")]
 	TooManyRequests,
+	#[error("Not found")]
+	NotFound,
 }
 
 pub(crate) const GET: Method = Method::GET;
@@ -96,6 +99,7 @@ impl Client {
 					error!("API query limit reached.");
 					Error::TooManyRequests
 				}
+				Some(StatusCode::NOT_FOUND) => Error::NotFound,
 				status => {
 					error!("Request failed with status {}", status.unwrap_or_default());
 					Error::Request(err)
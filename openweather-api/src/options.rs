@@ -111,6 +111,7 @@ impl From<&Language> for &'static str {
 	}
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for Language {
 	fn default() -> Self {
 		Self::English
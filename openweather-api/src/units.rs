@@ -13,6 +13,7 @@ pub enum Units {
 	Imperial,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for Units {
 	fn default() -> Self {
 		Self::Standard
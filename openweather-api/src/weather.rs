@@ -176,11 +176,11 @@ pub struct WeatherResponse {
 impl Client {
 	api_route! {
 		/// Find the weather at the given coordinates.
-		GET "/weather" pub weather_at(coordinates: Coordinates) -> WeatherResponse;
+		GET "/data/2.5/weather" pub weather_at(coordinates: Coordinates) -> WeatherResponse;
 	}
 
 	api_route! {
 		/// Find the weather in an area with a given latitude and longitude
-		GET "/weather" pub weather(#[to_string] lat: f64, #[to_string] lon: f64) -> WeatherResponse;
+		GET "/data/2.5/weather" pub weather(#[to_string] lat: f64, #[to_string] lon: f64) -> WeatherResponse;
 	}
 }
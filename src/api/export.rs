@@ -0,0 +1,258 @@
+//! moving tracked universities between accounts, or in and out of spreadsheets
+
+use actix_web::{
+	error::{ErrorBadRequest, ErrorUnauthorized},
+	http::header::{ContentDisposition, DispositionParam, DispositionType},
+	web::{self, ServiceConfig},
+	HttpRequest, HttpResponse, Responder, Result,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	db::Pool,
+	models::{
+		deadline::Deadline,
+		export::{CsvRow, Export, ExportedUniversity, Preferences},
+		profile::AdmissionsProfile,
+		tracked::Tracking,
+		user::User,
+	},
+};
+
+use super::{user::get_uid, IntoHttpError};
+
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum Format {
+	#[default]
+	Json,
+	Csv,
+}
+
+#[derive(Debug, Deserialize)]
+struct FormatParams {
+	#[serde(default)]
+	format: Format,
+}
+
+async fn export(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	query: web::Query<FormatParams>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	let user = User::load(con.as_ref(), uid)
+		.await
+		.into_500()?
+		.ok_or_else(|| ErrorUnauthorized("user not found"))?;
+
+	let mut tracking = Tracking::load_all(con.as_ref(), uid).await.into_500()?;
+
+	let mut universities = Vec::with_capacity(user.universities.len());
+	for university in user
+		.load_universities(con.as_ref())
+		.await
+		.into_500()?
+		.unwrap_or_default()
+	{
+		let tracking = tracking.remove(&university.id).unwrap_or_default();
+		let deadlines = Deadline::load_all(con.as_ref(), uid, university.id)
+			.await
+			.into_500()?;
+
+		universities.push(ExportedUniversity {
+			id: Some(university.id),
+			unitid: university.unitid,
+			name: Some(university.name),
+			website: Some(university.website),
+			notes: tracking.notes,
+			rating: tracking.rating,
+			category: tracking.category,
+			tags: tracking.tags,
+			deadlines: deadlines.into_iter().map(Into::into).collect(),
+		});
+	}
+
+	let preferences = Preferences {
+		units: user.metadata.units,
+		timezone: user.metadata.timezone,
+	};
+
+	let (body, content_type, extension) = match query.format {
+		Format::Json => {
+			let export = Export {
+				preferences: Some(preferences),
+				profile: AdmissionsProfile::load(con.as_ref(), uid)
+					.await
+					.into_500()?,
+				universities,
+			};
+
+			(
+				serde_json::to_vec_pretty(&export).into_500()?,
+				"application/json",
+				"json",
+			)
+		}
+		Format::Csv => {
+			let mut writer = csv::Writer::from_writer(Vec::new());
+			for university in universities {
+				let row = CsvRow::new(university, Some(&preferences)).into_500()?;
+				writer.serialize(row).into_500()?;
+			}
+
+			(writer.into_inner().into_500()?, "text/csv", "csv")
+		}
+	};
+
+	Ok(HttpResponse::Ok()
+		.content_type(content_type)
+		.insert_header(ContentDisposition {
+			disposition: DispositionType::Attachment,
+			parameters: vec![DispositionParam::Filename(format!("clime.{}", extension))],
+		})
+		.body(body))
+}
+
+#[derive(Debug, Serialize)]
+struct ImportReport {
+	/// how many universities were found and tracked
+	imported: usize,
+	/// the universities that couldn't be found
+	unmatched: Vec<String>,
+}
+
+fn parse(format: Format, body: &[u8]) -> Result<Export> {
+	match format {
+		Format::Json => serde_json::from_slice(body).map_err(ErrorBadRequest),
+		Format::Csv => {
+			let mut preferences = None;
+			let mut universities = Vec::new();
+			for (i, row) in csv::Reader::from_reader(body)
+				.deserialize::<CsvRow>()
+				.enumerate()
+			{
+				// the header is line 1
+				let line = i + 2;
+				let row: CsvRow =
+					row.map_err(|err| ErrorBadRequest(format!("line {}: {}", line, err)))?;
+				if preferences.is_none() {
+					preferences = row.preferences();
+				}
+
+				universities.push(
+					row.try_into()
+						.map_err(|err| ErrorBadRequest(format!("line {}: {}", line, err)))?,
+				);
+			}
+
+			Ok(Export {
+				preferences,
+				profile: None,
+				universities,
+			})
+		}
+	}
+}
+
+/// tracks every university that can be found, adding to what's already there.
+/// notes, ratings and tags are replaced, and deadlines are added unless they're already there.
+/// if anything fails, nothing is imported
+async fn import(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	query: web::Query<FormatParams>,
+	body: web::Bytes,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	let import = parse(query.format, &body)?;
+
+	// check everything before changing anything
+	let mut tracking = Vec::with_capacity(import.universities.len());
+	for university in &import.universities {
+		let mut data = university.tracking();
+		if let Some(data) = &mut data {
+			data.validate()
+				.map_err(|err| ErrorBadRequest(format!("{}: {}", university.describe(), err)))?;
+		}
+
+		for deadline in &university.deadlines {
+			deadline
+				.validate()
+				.map_err(|err| ErrorBadRequest(format!("{}: {}", university.describe(), err)))?;
+		}
+
+		tracking.push(data);
+	}
+
+	if let Some(profile) = &import.profile {
+		profile.validate().map_err(ErrorBadRequest)?;
+	}
+
+	let mut user = User::load(con.as_ref(), uid)
+		.await
+		.into_500()?
+		.ok_or_else(|| ErrorUnauthorized("user not found"))?;
+
+	let mut report = ImportReport {
+		imported: 0,
+		unmatched: Vec::new(),
+	};
+
+	let mut found = Vec::with_capacity(import.universities.len());
+	for (university, tracking) in import.universities.into_iter().zip(tracking) {
+		match university.find(con.as_ref()).await.into_500()? {
+			Some(id) => found.push((id, university, tracking)),
+			None => report.unmatched.push(university.describe()),
+		}
+	}
+
+	let mut trans = con.begin().await.into_500()?;
+
+	for (id, university, tracking) in found {
+		Tracking::track(&mut trans, uid, id).await.into_500()?;
+		if let Some(tracking) = tracking {
+			Tracking::put(&mut trans, uid, id, &tracking)
+				.await
+				.into_500()?;
+		}
+
+		let existing = Deadline::load_all(&mut trans, uid, id).await.into_500()?;
+		for deadline in university.deadlines {
+			let duplicate = existing.iter().any(|existing| {
+				existing.kind == deadline.kind
+					&& existing.name == deadline.name
+					&& existing.due == deadline.due
+			});
+
+			if !duplicate {
+				Deadline::create(&mut trans, uid, id, deadline)
+					.await
+					.into_500()?;
+			}
+		}
+
+		report.imported += 1;
+	}
+
+	if let Some(preferences) = import.preferences {
+		user.metadata.units = preferences.units;
+		user.metadata.timezone = preferences.timezone;
+		user.update(&mut trans).await.into_500()?;
+	}
+
+	if let Some(profile) = import.profile {
+		profile.put(&mut trans, uid).await.into_500()?;
+	}
+
+	trans.commit().await.into_500()?;
+
+	Ok(HttpResponse::Ok().json(report))
+}
+
+pub(super) fn configure(cfg: &mut ServiceConfig) {
+	cfg.service(web::resource("/export").route(web::get().to(export)))
+		.service(web::resource("/import").route(web::post().to(import)));
+}
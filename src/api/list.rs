@@ -0,0 +1,562 @@
+use actix_web::{
+	error::{ErrorBadRequest, ErrorForbidden, ErrorNotFound},
+	web::{self, ServiceConfig},
+	HttpRequest, HttpResponse, Responder, Result,
+};
+use chrono::{Duration, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+	db::Pool,
+	models::{
+		list::{List, Role},
+		member::{Invitation, Member, MemberNote},
+		share::ShareLink,
+	},
+};
+
+use super::{
+	user::{get_uid, is_reordering},
+	IntoHttpError,
+};
+
+#[derive(Debug, Deserialize)]
+struct ListName {
+	name: String,
+}
+
+impl ListName {
+	fn validate(&self) -> Result<()> {
+		if self.name.trim().is_empty() {
+			Err(ErrorBadRequest("name cannot be empty"))
+		} else {
+			Ok(())
+		}
+	}
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdateUniv {
+	id: i64,
+}
+
+async fn load(con: &Pool, uid: Uuid, id: i64) -> Result<List> {
+	List::load(con, uid, id)
+		.await
+		.into_500()?
+		.ok_or_else(|| ErrorNotFound("list not found"))
+}
+
+/// checks that whoever loaded the list is allowed to do what `role` can
+fn require(list: &List, role: Role) -> Result<()> {
+	if list.role >= role {
+		Ok(())
+	} else {
+		Err(ErrorForbidden(match role {
+			Role::Viewer => "only members can do that",
+			Role::Editor => "only editors can do that",
+			Role::Owner => "only the owner can do that",
+		}))
+	}
+}
+
+async fn get_all(con: web::Data<Pool>, req: HttpRequest) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	List::load_all(con.as_ref(), uid)
+		.await
+		.into_500()
+		.map(|lists| HttpResponse::Ok().json(lists))
+}
+
+async fn create(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	data: web::Json<ListName>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+	data.validate()?;
+
+	List::create(con.as_ref(), uid, data.0.name)
+		.await
+		.into_500()
+		.map(|list| HttpResponse::Created().json(list))
+}
+
+async fn get(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	load(&con, uid, path.into_inner())
+		.await
+		.map(|list| HttpResponse::Ok().json(list))
+}
+
+async fn rename(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+	data: web::Json<ListName>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+	data.validate()?;
+
+	let mut list = load(&con, uid, path.into_inner()).await?;
+	require(&list, Role::Editor)?;
+	list.rename(con.as_ref(), data.0.name).await.into_500()?;
+
+	Ok(HttpResponse::Ok().json(list))
+}
+
+async fn delete(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	let list = load(&con, uid, path.into_inner()).await?;
+	require(&list, Role::Owner)?;
+	if list.is_default {
+		return Err(ErrorBadRequest("the default list cannot be deleted"));
+	}
+
+	list.delete(con.as_ref())
+		.await
+		.into_500()
+		.map(|_| HttpResponse::Ok().body(()))
+}
+
+async fn get_universities(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	let list = load(&con, uid, path.into_inner()).await?;
+
+	list.load_universities(con.as_ref())
+		.await
+		.into_500()
+		.map(|universities| HttpResponse::Ok().json(universities))
+}
+
+/// the default list is changed by tracking universities, so it can't be changed directly
+fn check_not_default(list: &List) -> Result<()> {
+	if list.is_default {
+		Err(ErrorBadRequest(
+			"the default list is changed by tracking or untracking universities",
+		))
+	} else {
+		Ok(())
+	}
+}
+
+async fn add_university(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+	data: web::Json<UpdateUniv>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	let list = load(&con, uid, path.into_inner()).await?;
+	require(&list, Role::Editor)?;
+	check_not_default(&list)?;
+
+	sqlx::query!("SELECT id FROM universities WHERE id = $1", data.id)
+		.fetch_optional(con.as_ref())
+		.await
+		.into_500()?
+		.ok_or_else(|| ErrorNotFound("university not found"))?;
+
+	List::add(con.as_ref(), list.id, data.id)
+		.await
+		.into_500()
+		.map(|_| HttpResponse::Ok().body(()))
+}
+
+async fn remove_university(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+	data: web::Json<UpdateUniv>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	let list = load(&con, uid, path.into_inner()).await?;
+	require(&list, Role::Editor)?;
+	check_not_default(&list)?;
+
+	if List::remove(con.as_ref(), list.id, data.id)
+		.await
+		.into_500()?
+	{
+		Ok(HttpResponse::Ok().body(()))
+	} else {
+		Err(ErrorNotFound("university not in list"))
+	}
+}
+
+async fn reorder(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+	ids: web::Json<Vec<i64>>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	let mut list = load(&con, uid, path.into_inner()).await?;
+	require(&list, Role::Editor)?;
+	check_not_default(&list)?;
+
+	if !is_reordering(&ids, &list.universities) {
+		return Err(ErrorBadRequest(
+			"order must list every university in the list exactly once",
+		));
+	}
+
+	list.reorder(con.as_ref(), ids.0).await.into_500()?;
+
+	Ok(HttpResponse::Ok().json(list))
+}
+
+#[derive(Debug, Deserialize)]
+struct ShareParams {
+	/// `None` for a link that never expires, otherwise 1 to 365
+	expires_in_days: Option<i64>,
+}
+
+async fn get_shares(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	let list = load(&con, uid, path.into_inner()).await?;
+	require(&list, Role::Owner)?;
+
+	ShareLink::load_all(con.as_ref(), list.id)
+		.await
+		.into_500()
+		.map(|links| HttpResponse::Ok().json(links))
+}
+
+async fn share(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+	params: web::Json<ShareParams>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	if params
+		.expires_in_days
+		.is_some_and(|days| !(1..=365).contains(&days))
+	{
+		return Err(ErrorBadRequest("expires_in_days must be between 1 and 365"));
+	}
+
+	let list = load(&con, uid, path.into_inner()).await?;
+	require(&list, Role::Owner)?;
+	let expires_at = params
+		.expires_in_days
+		.map(|days| Utc::now() + Duration::days(days));
+
+	ShareLink::create(con.as_ref(), list.id, expires_at)
+		.await
+		.into_500()
+		.map(|link| HttpResponse::Created().json(link))
+}
+
+async fn revoke_share(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<(i64, String)>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+	let (id, token) = path.into_inner();
+
+	let list = load(&con, uid, id).await?;
+	require(&list, Role::Owner)?;
+
+	if ShareLink::revoke(con.as_ref(), list.id, &token)
+		.await
+		.into_500()?
+	{
+		Ok(HttpResponse::Ok().body(()))
+	} else {
+		Err(ErrorNotFound("share link not found"))
+	}
+}
+
+async fn get_members(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	let list = load(&con, uid, path.into_inner()).await?;
+
+	Member::load_all(con.as_ref(), list.id)
+		.await
+		.into_500()
+		.map(|members| HttpResponse::Ok().json(members))
+}
+
+#[derive(Debug, Deserialize)]
+struct NewMember {
+	username: String,
+	role: Role,
+}
+
+#[derive(Debug, Deserialize)]
+struct RoleUpdate {
+	role: Role,
+}
+
+/// a list has exactly one owner, who made it
+fn check_not_owner(role: Role) -> Result<()> {
+	if role == Role::Owner {
+		Err(ErrorBadRequest("members can only be editors or viewers"))
+	} else {
+		Ok(())
+	}
+}
+
+async fn invite(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+	data: web::Json<NewMember>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+	check_not_owner(data.role)?;
+
+	let list = load(&con, uid, path.into_inner()).await?;
+	require(&list, Role::Owner)?;
+	if list.is_default {
+		return Err(ErrorBadRequest("the default list cannot be shared"));
+	}
+
+	let user_id = sqlx::query!(
+		r#"SELECT id AS "id: Uuid" FROM users WHERE username = $1"#,
+		data.username
+	)
+	.fetch_optional(con.as_ref())
+	.await
+	.into_500()?
+	.ok_or_else(|| ErrorNotFound("user not found"))?
+	.id;
+
+	if Member::find(con.as_ref(), list.id, &data.username)
+		.await
+		.into_500()?
+		.is_some()
+	{
+		return Err(ErrorBadRequest("user is already a member or invited"));
+	}
+
+	Member::invite(con.as_ref(), list.id, user_id, data.role)
+		.await
+		.into_500()
+		.map(|_| HttpResponse::Created().body(()))
+}
+
+async fn update_member(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<(i64, String)>,
+	data: web::Json<RoleUpdate>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+	let (id, username) = path.into_inner();
+	check_not_owner(data.role)?;
+
+	let list = load(&con, uid, id).await?;
+	require(&list, Role::Owner)?;
+
+	let (user_id, role) = Member::find(con.as_ref(), list.id, &username)
+		.await
+		.into_500()?
+		.ok_or_else(|| ErrorNotFound("member not found"))?;
+	check_not_owner(role)?;
+
+	Member::set_role(con.as_ref(), list.id, user_id, data.role)
+		.await
+		.into_500()
+		.map(|_| HttpResponse::Ok().body(()))
+}
+
+/// removes a member or cancels an invitation; anyone but the owner can also leave
+async fn remove_member(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<(i64, String)>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+	let (id, username) = path.into_inner();
+
+	let list = load(&con, uid, id).await?;
+
+	let (user_id, role) = Member::find(con.as_ref(), list.id, &username)
+		.await
+		.into_500()?
+		.ok_or_else(|| ErrorNotFound("member not found"))?;
+
+	if role == Role::Owner {
+		return Err(ErrorBadRequest("the owner cannot leave their own list"));
+	} else if user_id != uid {
+		require(&list, Role::Owner)?;
+	}
+
+	Member::remove(con.as_ref(), list.id, user_id)
+		.await
+		.into_500()
+		.map(|_| HttpResponse::Ok().body(()))
+}
+
+async fn get_notes(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	let list = load(&con, uid, path.into_inner()).await?;
+
+	MemberNote::load_all(con.as_ref(), list.id)
+		.await
+		.into_500()
+		.map(|notes| HttpResponse::Ok().json(notes))
+}
+
+#[derive(Debug, Deserialize)]
+struct NotesUpdate {
+	/// `None` or empty to clear them
+	notes: Option<String>,
+}
+
+/// sets the member's own notes on a university in the list. viewers can keep notes too
+async fn update_notes(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<(i64, i64)>,
+	data: web::Json<NotesUpdate>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+	let (id, university_id) = path.into_inner();
+
+	let list = load(&con, uid, id).await?;
+	if !list.universities.contains(&university_id) {
+		return Err(ErrorNotFound("university not in list"));
+	}
+
+	let notes = data
+		.notes
+		.as_deref()
+		.filter(|notes| !notes.trim().is_empty());
+
+	MemberNote::put(con.as_ref(), list.id, university_id, uid, notes)
+		.await
+		.into_500()
+		.map(|_| HttpResponse::Ok().body(()))
+}
+
+async fn get_invitations(con: web::Data<Pool>, req: HttpRequest) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	Invitation::load_all(con.as_ref(), uid)
+		.await
+		.into_500()
+		.map(|invitations| HttpResponse::Ok().json(invitations))
+}
+
+async fn accept_invitation(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	if Invitation::accept(con.as_ref(), path.into_inner(), uid)
+		.await
+		.into_500()?
+	{
+		Ok(HttpResponse::Ok().body(()))
+	} else {
+		Err(ErrorNotFound("invitation not found"))
+	}
+}
+
+async fn decline_invitation(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	if Invitation::decline(con.as_ref(), path.into_inner(), uid)
+		.await
+		.into_500()?
+	{
+		Ok(HttpResponse::Ok().body(()))
+	} else {
+		Err(ErrorNotFound("invitation not found"))
+	}
+}
+
+pub(super) fn configure(cfg: &mut ServiceConfig) {
+	cfg.service(
+		web::resource("")
+			.route(web::get().to(get_all))
+			.route(web::post().to(create)),
+	)
+	.service(web::resource("/invitations").route(web::get().to(get_invitations)))
+	.service(
+		web::resource("/invitations/{id}")
+			.route(web::post().to(accept_invitation))
+			.route(web::delete().to(decline_invitation)),
+	)
+	.service(
+		web::resource("/{id}")
+			.route(web::get().to(get))
+			.route(web::put().to(rename))
+			.route(web::delete().to(delete)),
+	)
+	.service(
+		web::resource("/{id}/universities")
+			.route(web::get().to(get_universities))
+			.route(web::put().to(add_university))
+			.route(web::delete().to(remove_university)),
+	)
+	.service(web::resource("/{id}/order").route(web::put().to(reorder)))
+	.service(
+		web::resource("/{id}/shares")
+			.route(web::get().to(get_shares))
+			.route(web::post().to(share)),
+	)
+	.service(web::resource("/{id}/shares/{token}").route(web::delete().to(revoke_share)))
+	.service(
+		web::resource("/{id}/members")
+			.route(web::get().to(get_members))
+			.route(web::post().to(invite)),
+	)
+	.service(
+		web::resource("/{id}/members/{username}")
+			.route(web::put().to(update_member))
+			.route(web::delete().to(remove_member)),
+	)
+	.service(web::resource("/{id}/notes").route(web::get().to(get_notes)))
+	.service(
+		web::resource("/{id}/universities/{university_id}/notes")
+			.route(web::put().to(update_notes)),
+	);
+}
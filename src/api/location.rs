@@ -0,0 +1,282 @@
+//! custom locations, which get the same weather and forecasts as universities
+
+use actix_web::{
+	error::{ErrorBadRequest, ErrorNotFound},
+	web::{self, ServiceConfig},
+	HttpRequest, HttpResponse, Responder, Result,
+};
+use chrono::Utc;
+use openweather_api::{Client, Coordinates};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+	db::Pool,
+	geo,
+	models::{
+		daily,
+		forecast::Forecast,
+		location::{Location, LocationDailyForecast, LocationForecast, LocationWeather},
+		timezone::Timezone,
+		weather::Weather,
+	},
+};
+
+use super::{
+	university::{check_coordinates, geocode_zip},
+	user::get_uid,
+	IntoHttpError,
+};
+
+/// the most locations a user can have, since each one is fetched every hour
+const MAX_LOCATIONS: i64 = 10;
+
+#[derive(Debug, Deserialize)]
+struct NewLocation {
+	name: String,
+	lat: Option<f64>,
+	lon: Option<f64>,
+	/// a US zip code, instead of `lat` and `lon`
+	zip: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LocationName {
+	name: String,
+}
+
+async fn load(con: &Pool, uid: Uuid, id: i64) -> Result<Location> {
+	Location::load(con, uid, id)
+		.await
+		.into_500()?
+		.ok_or_else(|| ErrorNotFound("location not found"))
+}
+
+/// what OpenWeather calls the place at some coordinates, if anything
+async fn reverse_geocode(client: &Client, coords: &Coordinates) -> Option<String> {
+	match client.reverse(1, coords).await {
+		Ok(places) => places.into_iter().next().map(|place| match place.state {
+			Some(state) => format!("{}, {}, {}", place.name, state, place.country),
+			None => format!("{}, {}", place.name, place.country),
+		}),
+		Err(err) => {
+			log::warn!("Error reverse geocoding: {}", err);
+			None
+		}
+	}
+}
+
+async fn get_all(con: web::Data<Pool>, req: HttpRequest) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	Location::load_all(con.as_ref(), uid)
+		.await
+		.into_500()
+		.map(|locations| HttpResponse::Ok().json(locations))
+}
+
+async fn create(
+	con: web::Data<Pool>,
+	client: web::Data<Client>,
+	req: HttpRequest,
+	data: web::Json<NewLocation>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+	let data = data.0;
+
+	if data.name.trim().is_empty() {
+		return Err(ErrorBadRequest("name cannot be empty"));
+	}
+
+	if Location::count(con.as_ref(), uid).await.into_500()? >= MAX_LOCATIONS {
+		return Err(ErrorBadRequest(format!(
+			"users can have at most {} locations",
+			MAX_LOCATIONS
+		)));
+	}
+
+	let (coords, geocoded_name) = match (data.lat, data.lon, data.zip) {
+		(Some(latitude), Some(longitude), None) => {
+			let coords = check_coordinates(latitude, longitude)?;
+			let name = reverse_geocode(&client, &coords).await;
+			(coords, name)
+		}
+		(None, None, Some(zip)) => {
+			let location = geocode_zip(&client, &zip).await?;
+			let name = format!("{}, {}", location.name, location.country);
+			(location.coordinates, Some(name))
+		}
+		_ => return Err(ErrorBadRequest("either lat and lon or zip is required")),
+	};
+
+	let timezone = Timezone(geo::timezone::resolve(&coords));
+
+	Location::create(
+		con.as_ref(),
+		uid,
+		data.name,
+		geocoded_name,
+		&coords,
+		timezone,
+	)
+	.await
+	.into_500()
+	.map(|location| HttpResponse::Created().json(location))
+}
+
+async fn get(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	load(&con, uid, path.into_inner())
+		.await
+		.map(|location| HttpResponse::Ok().json(location))
+}
+
+async fn rename(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+	data: web::Json<LocationName>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	if data.name.trim().is_empty() {
+		return Err(ErrorBadRequest("name cannot be empty"));
+	}
+
+	let mut location = load(&con, uid, path.into_inner()).await?;
+	location
+		.rename(con.as_ref(), data.0.name)
+		.await
+		.into_500()?;
+
+	Ok(HttpResponse::Ok().json(location))
+}
+
+async fn delete(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	load(&con, uid, path.into_inner())
+		.await?
+		.delete(con.as_ref())
+		.await
+		.into_500()
+		.map(|_| HttpResponse::Ok().body(()))
+}
+
+/// the most recent weather from the last 4 hours, fetching it if there isn't any
+pub(super) async fn location_weather(
+	con: &Pool,
+	client: &Client,
+	location: &Location,
+) -> Result<Weather> {
+	let recent = location
+		.get_weather(con)
+		.await
+		.into_500()?
+		.filter(|data| Utc::now().signed_duration_since(data.time).num_hours() <= 4);
+
+	if let Some(weather) = recent {
+		return Ok(weather);
+	}
+
+	let weather = Weather::fetch(client, location.id, &location.coordinates())
+		.await
+		.into_500()?;
+	Location::put_weather(con, &weather).await.into_500()?;
+
+	Ok(weather)
+}
+
+/// today's and future forecasts, fetching them if there are none saved
+async fn load_forecasts(con: &Pool, client: &Client, location: &Location) -> Result<Vec<Forecast>> {
+	let forecasts = location.get_forecasts(con).await.into_500()?;
+	if !forecasts.is_empty() {
+		return Ok(forecasts);
+	}
+
+	for forecast in Forecast::fetch(client.clone(), location.id, &location.coordinates(), 40)
+		.await
+		.into_500()?
+	{
+		Location::put_forecast(con, &forecast).await.into_500()?;
+	}
+
+	location.get_forecasts(con).await.into_500()
+}
+
+async fn weather(
+	con: web::Data<Pool>,
+	client: web::Data<Client>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	let location = load(&con, uid, path.into_inner()).await?;
+
+	let weather = location_weather(&con, &client, &location).await?;
+
+	Ok(HttpResponse::Ok().json(LocationWeather::from(weather)))
+}
+
+async fn forecast(
+	con: web::Data<Pool>,
+	client: web::Data<Client>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	let location = load(&con, uid, path.into_inner()).await?;
+
+	let forecasts = load_forecasts(&con, &client, &location).await?;
+
+	let forecasts: Vec<_> = forecasts.into_iter().map(LocationForecast::from).collect();
+
+	Ok(HttpResponse::Ok().json(forecasts))
+}
+
+async fn forecast_daily(
+	con: web::Data<Pool>,
+	client: web::Data<Client>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	let location = load(&con, uid, path.into_inner()).await?;
+	let forecasts = load_forecasts(&con, &client, &location).await?;
+
+	let daily: Vec<_> = daily::summarize_days(&forecasts, &location.timezone)
+		.into_iter()
+		.map(LocationDailyForecast::from)
+		.collect();
+
+	Ok(HttpResponse::Ok().json(daily))
+}
+
+pub(super) fn configure(cfg: &mut ServiceConfig) {
+	cfg.service(
+		web::resource("")
+			.route(web::get().to(get_all))
+			.route(web::post().to(create)),
+	)
+	.service(
+		web::resource("/{id}")
+			.route(web::get().to(get))
+			.route(web::put().to(rename))
+			.route(web::delete().to(delete)),
+	)
+	.service(web::resource("/{id}/weather").route(web::get().to(weather)))
+	.service(web::resource("/{id}/forecast").route(web::get().to(forecast)))
+	.service(web::resource("/{id}/forecast/daily").route(web::get().to(forecast_daily)));
+}
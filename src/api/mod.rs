@@ -6,6 +6,10 @@ use actix_web::{
 };
 use log::info;
 
+mod export;
+mod list;
+mod location;
+mod shared;
 mod university;
 mod user;
 
@@ -26,5 +30,6 @@ pub fn configure(cfg: &mut ServiceConfig) {
 		actix_web::error::ErrorBadRequest(err)
 	}))
 	.service(web::scope("/university").configure(university::configure))
-	.service(web::scope("/user").configure(user::configure));
+	.service(web::scope("/user").configure(user::configure))
+	.service(web::scope("/shared").configure(shared::configure));
 }
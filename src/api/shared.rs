@@ -0,0 +1,77 @@
+//! lists shared by link, which anyone can view without an account
+
+use actix_web::{
+	error::ErrorNotFound,
+	web::{self, ServiceConfig},
+	HttpResponse, Responder, Result,
+};
+use openweather_api::Client;
+use serde::Serialize;
+
+use crate::{
+	db::Pool,
+	models::{
+		forecast::Forecast, list::List, share::ShareLink, university::University, weather::Weather,
+	},
+};
+
+use super::{
+	university::{current_weather, load_forecasts},
+	IntoHttpError,
+};
+
+#[derive(Debug, Serialize)]
+struct SharedUniversity {
+	university: University,
+	/// the current weather, if it could be loaded
+	weather: Option<Weather>,
+	/// empty if they couldn't be loaded
+	forecasts: Vec<Forecast>,
+}
+
+#[derive(Debug, Serialize)]
+struct SharedList {
+	name: String,
+	universities: Vec<SharedUniversity>,
+}
+
+async fn get(
+	con: web::Data<Pool>,
+	client: web::Data<Client>,
+	path: web::Path<String>,
+) -> Result<impl Responder> {
+	// revoked, expired and made-up tokens all look the same
+	let list_id = ShareLink::resolve(con.as_ref(), &path)
+		.await
+		.into_500()?
+		.ok_or_else(|| ErrorNotFound("shared list not found"))?;
+
+	let list = List::load_by_id(con.as_ref(), list_id)
+		.await
+		.into_500()?
+		.ok_or_else(|| ErrorNotFound("shared list not found"))?;
+
+	let mut universities = Vec::with_capacity(list.universities.len());
+	for university in list.load_universities(con.as_ref()).await.into_500()? {
+		// like the weather, missing forecasts shouldn't hide the rest of the list
+		let forecasts = load_forecasts(&con, &client, university.id)
+			.await
+			.map_err(|err| log::warn!("Error loading forecasts for {}: {}", university.id, err))
+			.unwrap_or_default();
+
+		universities.push(SharedUniversity {
+			weather: current_weather(&con, &client, university.id).await?,
+			forecasts,
+			university,
+		});
+	}
+
+	Ok(HttpResponse::Ok().json(SharedList {
+		name: list.name,
+		universities,
+	}))
+}
+
+pub(super) fn configure(cfg: &mut ServiceConfig) {
+	cfg.service(web::resource("/{token}").route(web::get().to(get)));
+}
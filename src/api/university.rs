@@ -1,15 +1,30 @@
 use actix_web::{
-	error::{ErrorInternalServerError, ErrorNotFound},
+	error::{ErrorBadRequest, ErrorInternalServerError, ErrorNotFound},
 	web::{self, ServiceConfig},
 	HttpResponse, Responder, Result,
 };
-use chrono::Utc;
-use openweather_api::Client;
+use chrono::{Duration, Utc};
+use openweather_api::{geocoding::ZipResponse, Client, Coordinates};
 use serde::{Deserialize, Serialize};
 
 use crate::{
 	db::Pool,
-	models::{forecast::Forecast, university::University, weather::Weather},
+	models::{
+		accuracy::Accuracy,
+		admissions::AdmissionsSummary,
+		browse::{Filters, Order, Sort},
+		climatology::climatology,
+		compare::{Comparison, Loaded},
+		daily,
+		forecast::Forecast,
+		interpolate,
+		nearby::Nearby,
+		outcomes::Outcomes,
+		search,
+		stats::{Stats, Trend},
+		university::{Consideration, University},
+		weather::Weather,
+	},
 };
 
 use super::IntoHttpError;
@@ -19,45 +34,251 @@ pub struct IdParams {
 	id: i64,
 }
 
-async fn get_university(con: &Pool, params: web::Path<IdParams>) -> Result<University> {
-	match University::load(con, params.id).await {
+async fn get_university(con: &Pool, id: i64) -> Result<University> {
+	match University::load(con, id).await {
 		Ok(Some(univ)) => Ok(univ),
 		Ok(None) => Err(ErrorNotFound("university not found")),
 		Err(err) => Err(ErrorInternalServerError(err)),
 	}
 }
 
+/// a university, with its computed admissions numbers
+#[derive(Debug, Serialize)]
+struct UniversityResponse {
+	#[serde(flatten)]
+	university: University,
+	admissions: AdmissionsSummary,
+}
+
+impl From<University> for UniversityResponse {
+	fn from(university: University) -> Self {
+		Self {
+			admissions: AdmissionsSummary::new(&university),
+			university,
+		}
+	}
+}
+
 async fn get(con: web::Data<Pool>, params: web::Path<IdParams>) -> Result<impl Responder> {
-	let university = get_university(&con, params).await?;
+	let university = get_university(&con, params.id).await?;
 
-	Ok(HttpResponse::Ok().json(university))
+	Ok(HttpResponse::Ok().json(UniversityResponse::from(university)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UnitidParams {
+	unitid: i64,
+}
+
+async fn get_by_unitid(
+	con: web::Data<Pool>,
+	params: web::Path<UnitidParams>,
+) -> Result<impl Responder> {
+	let id = University::id_from_unitid(con.as_ref(), params.unitid)
+		.await
+		.into_500()?
+		.ok_or_else(|| ErrorNotFound("university not found"))?;
+
+	let university = get_university(&con, id).await?;
+
+	Ok(HttpResponse::Ok().json(UniversityResponse::from(university)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BrowseParams {
+	/// comma-separated state abbreviations
+	state: Option<String>,
+	min_enrollment: Option<i64>,
+	max_enrollment: Option<i64>,
+	max_price_out_of_state: Option<i64>,
+	open_admission: Option<bool>,
+	max_acceptance_rate: Option<f64>,
+	considers_test_scores: Option<Consideration>,
+	/// total SAT scores, like `1200-1400`
+	sat_range: Option<String>,
+	#[serde(default)]
+	sort: Sort,
+	#[serde(default)]
+	order: Order,
+	/// starts at 1
+	#[serde(default = "SearchParams::default_page")]
+	page: i64,
+	#[serde(default = "SearchParams::default_limit")]
+	limit: i64,
+}
+
+fn parse_sat_range(range: &str) -> Option<(i64, i64)> {
+	let (low, high) = range.split_once('-')?;
+	let (low, high) = (low.trim().parse().ok()?, high.trim().parse().ok()?);
+
+	(low <= high).then_some((low, high))
+}
+
+async fn browse(con: web::Data<Pool>, query: web::Query<BrowseParams>) -> Result<impl Responder> {
+	if query.page < 1 {
+		return Err(ErrorBadRequest("page must be at least 1"));
+	}
+
+	if !(1..=50).contains(&query.limit) {
+		return Err(ErrorBadRequest("limit must be between 1 and 50"));
+	}
+
+	let query = query.into_inner();
+
+	let sat_range = match query.sat_range {
+		Some(range) => Some(
+			parse_sat_range(&range)
+				.ok_or_else(|| ErrorBadRequest("sat_range must be like 1200-1400"))?,
+		),
+		None => None,
+	};
+
+	let filters = Filters {
+		states: query
+			.state
+			.iter()
+			.flat_map(|states| states.split(','))
+			.map(|state| state.trim().to_uppercase())
+			.filter(|state| !state.is_empty())
+			.collect(),
+		min_enrollment: query.min_enrollment,
+		max_enrollment: query.max_enrollment,
+		max_price_out_of_state: query.max_price_out_of_state,
+		open_admission: query.open_admission,
+		max_acceptance_rate: query.max_acceptance_rate,
+		considers_test_scores: query.considers_test_scores,
+		sat_range,
+	};
+
+	let offset = (query.page - 1) * query.limit;
+	let browse = filters
+		.browse(&con, query.sort, query.order, query.limit, offset)
+		.await
+		.into_500()?;
+
+	Ok(HttpResponse::Ok().json(browse))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NearParams {
+	lat: Option<f64>,
+	lon: Option<f64>,
+	/// a US zip code, instead of `lat` and `lon`
+	zip: Option<String>,
+	#[serde(default = "NearParams::default_radius_km")]
+	radius_km: f64,
+	#[serde(default = "NearParams::default_limit")]
+	limit: usize,
+}
+
+impl NearParams {
+	pub(super) const fn default_radius_km() -> f64 {
+		100.0
+	}
+
+	pub(super) const fn default_limit() -> usize {
+		20
+	}
+}
+
+/// looks up a US zip code
+pub(super) async fn geocode_zip(client: &Client, zip: &str) -> Result<ZipResponse> {
+	if zip.len() != 5 || !zip.bytes().all(|b| b.is_ascii_digit()) {
+		return Err(ErrorBadRequest("zip must be a 5-digit zip code"));
+	}
+
+	match client.zip(format!("{},US", zip)).await {
+		Ok(location) => Ok(location),
+		Err(openweather_api::Error::NotFound) => Err(ErrorNotFound("zip code not found")),
+		Err(err) => Err(ErrorInternalServerError(err)),
+	}
+}
+
+/// finds the coordinates of a US zip code
+pub(super) async fn locate_zip(client: &Client, zip: &str) -> Result<Coordinates> {
+	geocode_zip(client, zip)
+		.await
+		.map(|location| location.coordinates)
+}
+
+pub(super) fn check_coordinates(latitude: f64, longitude: f64) -> Result<Coordinates> {
+	if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+		return Err(ErrorBadRequest("lat and lon must be a valid location"));
+	}
+
+	Ok(Coordinates {
+		latitude,
+		longitude,
+	})
+}
+
+/// the universities within `radius_km` of `center`, closest first
+pub(super) async fn load_nearby(
+	con: &Pool,
+	center: &Coordinates,
+	radius_km: f64,
+	limit: usize,
+) -> Result<Vec<Nearby>> {
+	if !(0.0..=5000.0).contains(&radius_km) {
+		return Err(ErrorBadRequest("radius_km must be between 0 and 5000"));
+	}
+
+	if !(1..=100).contains(&limit) {
+		return Err(ErrorBadRequest("limit must be between 1 and 100"));
+	}
+
+	Nearby::load(con, center, radius_km, limit).await.into_500()
+}
+
+async fn near(
+	con: web::Data<Pool>,
+	client: web::Data<Client>,
+	query: web::Query<NearParams>,
+) -> Result<impl Responder> {
+	let center = match (query.lat, query.lon, &query.zip) {
+		(Some(latitude), Some(longitude), None) => check_coordinates(latitude, longitude)?,
+		(None, None, Some(zip)) => locate_zip(&client, zip).await?,
+		_ => return Err(ErrorBadRequest("either lat and lon or zip is required")),
+	};
+
+	let nearby = load_nearby(&con, &center, query.radius_km, query.limit).await?;
+
+	Ok(HttpResponse::Ok().json(nearby))
 }
 
 #[derive(Debug, Deserialize)]
 pub struct SearchParams {
 	search: String,
+	/// starts at 1
+	#[serde(default = "SearchParams::default_page")]
+	page: i64,
+	#[serde(default = "SearchParams::default_limit")]
+	limit: i64,
 }
 
-#[derive(Debug, Serialize)]
-pub struct SearchUniv {
-	id: i64,
-	name: String,
+impl SearchParams {
+	const fn default_page() -> i64 {
+		1
+	}
+
+	const fn default_limit() -> i64 {
+		20
+	}
 }
 
 async fn search(con: web::Data<Pool>, query: web::Query<SearchParams>) -> Result<impl Responder> {
-	// like ignores case by default (in sqlite)
-	// TODO: look into using COLLATE to guarantee this?
-	let matches = sqlx::query_as!(
-		SearchUniv,
-		"SELECT id, name FROM universities WHERE
-			name LIKE '%' || $1 || '%' OR
-			aliases LIKE '%' || $1 || '%'
-		LIMIT 20",
-		query.search
-	)
-	.fetch_all(&**con)
-	.await
-	.into_500()?;
+	if query.page < 1 {
+		return Err(ErrorBadRequest("page must be at least 1"));
+	}
+
+	if !(1..=50).contains(&query.limit) {
+		return Err(ErrorBadRequest("limit must be between 1 and 50"));
+	}
+
+	let offset = (query.page - 1) * query.limit;
+	let matches = search::search(con.as_ref(), &query.search, query.limit, offset)
+		.await
+		.into_500()?;
 
 	Ok(HttpResponse::Ok().json(matches))
 }
@@ -71,78 +292,283 @@ async fn weather(
 		.await
 		.map_err(ErrorInternalServerError)?
 		.into_iter()
-		.filter(|data| {
-			Utc::now()
-				.naive_utc()
-				.signed_duration_since(data.time)
-				.num_hours() <= 4
-		})
+		.filter(|data| Utc::now().signed_duration_since(data.time).num_hours() <= 4)
 		.collect();
 
 	if weather.is_empty() {
-		let coords = University::get_coordinates(con.as_ref(), params.id)
-			.await
-			.map_err(ErrorInternalServerError)?
-			.ok_or_else(|| ErrorNotFound("university not found"))?;
+		weather.reserve_exact(1);
+		weather.push(fetch_weather(&con, &client, params.id).await?);
+	}
 
-		let data = Weather::fetch(&client, params.id, &coords)
-			.await
-			.map_err(ErrorInternalServerError)?;
+	Ok(HttpResponse::Ok().json(weather))
+}
+
+/// fetches and saves the current weather
+async fn fetch_weather(con: &Pool, client: &Client, id: i64) -> Result<Weather> {
+	let coords = University::get_coordinates(con, id)
+		.await
+		.map_err(ErrorInternalServerError)?
+		.ok_or_else(|| ErrorNotFound("university not found"))?;
+
+	let data = Weather::fetch(client, id, &coords)
+		.await
+		.map_err(ErrorInternalServerError)?;
+
+	data.put(con).await.map_err(ErrorInternalServerError)?;
+
+	Ok(data)
+}
 
-		data.put(con.as_ref())
+/// the most recent weather from the last 4 hours, fetching it if there isn't any.
+/// one university's weather being unavailable shouldn't hide the rest of a table,
+/// so fetch errors are logged and give `None`
+pub(super) async fn current_weather(
+	con: &Pool,
+	client: &Client,
+	id: i64,
+) -> Result<Option<Weather>> {
+	let recent = Weather::get_most_recent(con, id, 1)
+		.await
+		.into_500()?
+		.into_iter()
+		.find(|data| Utc::now().signed_duration_since(data.time).num_hours() <= 4);
+
+	Ok(match recent {
+		Some(weather) => Some(weather),
+		None => fetch_weather(con, client, id)
 			.await
-			.map_err(ErrorInternalServerError)?;
+			.map_err(|err| log::warn!("Error fetching weather for {}: {}", id, err))
+			.ok(),
+	})
+}
 
-		weather.reserve_exact(1);
-		weather.push(data);
+#[derive(Debug, Deserialize, Default, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Resolution {
+	/// the 3-hour steps from the forecast API
+	#[default]
+	#[serde(rename = "3h")]
+	ThreeHour,
+	/// 3-hour steps interpolated to every hour
+	Hourly,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ForecastParams {
+	#[serde(default)]
+	resolution: Resolution,
+}
+
+/// loads today's and future forecasts, fetching them if there are none saved
+pub(super) async fn load_forecasts(con: &Pool, client: &Client, id: i64) -> Result<Vec<Forecast>> {
+	let forecasts = Forecast::get_all_since(con, id)
+		.await
+		.into_500()?
+		.ok_or_else(|| ErrorNotFound("university not found"))?
+		.into_iter()
+		.filter(|data| Utc::now().signed_duration_since(data.time).num_hours() <= 40)
+		.collect::<Vec<_>>();
+
+	if !forecasts.is_empty() {
+		return Ok(forecasts);
 	}
 
-	Ok(HttpResponse::Ok().json(weather))
+	let coords = University::get_coordinates(con, id)
+		.await
+		.into_500()?
+		.ok_or_else(|| ErrorNotFound("university not found"))?;
+
+	let forecasts = Forecast::fetch(client.clone(), id, &coords, 40)
+		.await
+		.into_500()?;
+
+	let mut trans = con.begin().await.into_500()?;
+
+	for f in forecasts.iter() {
+		f.put(&mut trans).await.into_500()?;
+	}
+
+	trans.commit().await.into_500()?;
+
+	Ok(forecasts)
 }
 
 async fn forecast(
 	con: web::Data<Pool>,
 	client: web::Data<Client>,
 	params: web::Path<IdParams>,
+	query: web::Query<ForecastParams>,
 ) -> Result<impl Responder> {
-	let mut forecasts = Forecast::get_all_since(con.as_ref(), params.id)
-		.await
-		.into_500()?
-		.ok_or_else(|| ErrorNotFound("university not found"))?
-		.into_iter()
-		.filter(|data| {
-			Utc::now()
-				.naive_utc()
-				.signed_duration_since(data.time)
-				.num_hours() <= 40
-		})
-		.collect::<Vec<_>>();
+	let mut forecasts = load_forecasts(&con, &client, params.id).await?;
 
-	if forecasts.is_empty() {
+	if query.resolution == Resolution::Hourly {
 		let coords = University::get_coordinates(con.as_ref(), params.id)
 			.await
 			.into_500()?
 			.ok_or_else(|| ErrorNotFound("university not found"))?;
 
-		forecasts = Forecast::fetch(Client::clone(&client), params.id, &coords, 40)
-			.await
-			.into_500()?;
+		forecasts = interpolate::hourly(&forecasts, &coords);
+	}
+
+	Ok(HttpResponse::Ok().json(forecasts))
+}
+
+async fn forecast_daily(
+	con: web::Data<Pool>,
+	client: web::Data<Client>,
+	params: web::Path<IdParams>,
+) -> Result<impl Responder> {
+	let forecasts = load_forecasts(&con, &client, params.id).await?;
+
+	let university = get_university(&con, params.id).await?;
+	Ok(HttpResponse::Ok().json(daily::summarize_days(&forecasts, &university.timezone)))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccuracyParams {
+	/// how many days of history to compare against
+	#[serde(default = "AccuracyParams::default_days")]
+	days: i64,
+}
+
+impl AccuracyParams {
+	const fn default_days() -> i64 {
+		30
+	}
+}
+
+async fn forecast_accuracy(
+	con: web::Data<Pool>,
+	params: web::Path<IdParams>,
+	query: web::Query<AccuracyParams>,
+) -> Result<impl Responder> {
+	if !(1..=365).contains(&query.days) {
+		return Err(ErrorBadRequest("days must be between 1 and 365"));
+	}
+
+	University::get_coordinates(con.as_ref(), params.id)
+		.await
+		.into_500()?
+		.ok_or_else(|| ErrorNotFound("university not found"))?;
+
+	let accuracy = Accuracy::load(con.as_ref(), params.id, query.days)
+		.await
+		.into_500()?;
+
+	Ok(HttpResponse::Ok().json(accuracy))
+}
+
+/// selectivity, price and test scores for every year of statistics, oldest first
+async fn trends(con: web::Data<Pool>, params: web::Path<IdParams>) -> Result<impl Responder> {
+	University::get_coordinates(con.as_ref(), params.id)
+		.await
+		.into_500()?
+		.ok_or_else(|| ErrorNotFound("university not found"))?;
+
+	let trends: Vec<_> = Stats::load_all(con.as_ref(), params.id)
+		.await
+		.into_500()?
+		.iter()
+		.map(Trend::from)
+		.collect();
+
+	Ok(HttpResponse::Ok().json(trends))
+}
+
+async fn outcomes(con: web::Data<Pool>, params: web::Path<IdParams>) -> Result<impl Responder> {
+	University::get_coordinates(con.as_ref(), params.id)
+		.await
+		.into_500()?
+		.ok_or_else(|| ErrorNotFound("university not found"))?;
+
+	let outcomes = Outcomes::load(con.as_ref(), params.id)
+		.await
+		.into_500()?
+		.ok_or_else(|| ErrorNotFound("no outcomes for this university"))?;
+
+	Ok(HttpResponse::Ok().json(outcomes))
+}
 
-		let mut trans = con.begin().await.into_500()?;
+#[derive(Debug, Deserialize)]
+pub struct CompareParams {
+	/// comma-separated university ids
+	ids: String,
+}
+
+/// the most universities that can be compared at once
+const MAX_COMPARE: usize = 5;
+
+/// climatology only looks at this many days of observations, so it doesn't load every one ever saved
+const CLIMATOLOGY_DAYS: i64 = 365;
+
+async fn compare(
+	con: web::Data<Pool>,
+	client: web::Data<Client>,
+	query: web::Query<CompareParams>,
+) -> Result<impl Responder> {
+	let mut ids = Vec::new();
+	for id in query.ids.split(',') {
+		let id = id
+			.trim()
+			.parse::<i64>()
+			.map_err(|_| ErrorBadRequest("ids must be comma-separated numbers"))?;
 
-		for f in forecasts.iter() {
-			f.put(&mut trans).await.into_500()?;
+		if !ids.contains(&id) {
+			ids.push(id);
 		}
+	}
 
-		trans.commit().await.into_500()?;
+	if !(2..=MAX_COMPARE).contains(&ids.len()) {
+		return Err(ErrorBadRequest(format!(
+			"between 2 and {} universities can be compared",
+			MAX_COMPARE
+		)));
 	}
 
-	Ok(HttpResponse::Ok().json(forecasts))
+	let mut loaded = Vec::with_capacity(ids.len());
+
+	for id in ids {
+		let university = get_university(&con, id).await?;
+
+		let weather = current_weather(&con, &client, id).await?;
+		// like the weather, missing forecasts shouldn't hide the other universities
+		let forecasts = load_forecasts(&con, &client, id)
+			.await
+			.map_err(|err| log::warn!("Error loading forecasts for {}: {}", id, err))
+			.unwrap_or_default();
+
+		let now = Utc::now();
+		let observations = Weather::get_between(
+			con.as_ref(),
+			id,
+			now - Duration::days(CLIMATOLOGY_DAYS),
+			now,
+		)
+		.await
+		.into_500()?;
+
+		loaded.push(Loaded {
+			daily: daily::summarize_days(&forecasts, &university.timezone),
+			climatology: climatology(&observations, &university.timezone),
+			university,
+			weather,
+		});
+	}
+
+	Ok(HttpResponse::Ok().json(Comparison::new(loaded)))
 }
 
 pub(super) fn configure(cfg: &mut ServiceConfig) {
-	cfg.service(web::resource("/search").route(web::get().to(search)))
+	cfg.service(web::resource("").route(web::get().to(browse)))
+		.service(web::resource("/search").route(web::get().to(search)))
+		.service(web::resource("/near").route(web::get().to(near)))
+		.service(web::resource("/compare").route(web::get().to(compare)))
+		.service(web::resource("/by-unitid/{unitid}").route(web::get().to(get_by_unitid)))
 		.service(web::resource("/{id}").route(web::get().to(get)))
 		.service(web::resource("/{id}/weather").route(web::get().to(weather)))
-		.service(web::resource("/{id}/forecast").route(web::get().to(forecast)));
+		.service(web::resource("/{id}/forecast").route(web::get().to(forecast)))
+		.service(web::resource("/{id}/forecast/daily").route(web::get().to(forecast_daily)))
+		.service(web::resource("/{id}/forecast/accuracy").route(web::get().to(forecast_accuracy)))
+		.service(web::resource("/{id}/trends").route(web::get().to(trends)))
+		.service(web::resource("/{id}/outcomes").route(web::get().to(outcomes)));
 }
@@ -5,14 +5,36 @@ use actix_web::{
 	web::{self, ServiceConfig},
 	HttpMessage, HttpRequest, HttpResponse, Responder, Result,
 };
+use futures_util::future;
 use rand::Rng;
 
 use crate::{
 	db::Pool,
-	models::user::{Authentication, Metadata, User},
+	geo,
+	models::{
+		admissions::AdmissionsSummary,
+		checklist::{ChecklistItem, ItemUpdate, Progress, UniversityProgress},
+		deadline::{Deadline, DeadlineData},
+		fit::Classification,
+		home::{FromHome, Home, HomeComparison},
+		local_time::LocalTimeWeather,
+		location::Location,
+		profile::AdmissionsProfile,
+		timezone::Timezone,
+		tracked::{Tracking, TrackingData, TrackingFilter},
+		university::University,
+		user::{Authentication, Metadata, User},
+	},
+};
+
+use super::{
+	location::location_weather,
+	university::{check_coordinates, current_weather, load_nearby, locate_zip, NearParams},
+	IntoHttpError,
 };
 use chrono::{Duration, Utc};
 use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use openweather_api::{Client, Coordinates};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -33,10 +55,20 @@ struct Claims {
 	uid: Uuid,
 }
 
-fn get_uid(req: &HttpRequest) -> Uuid {
+pub(super) fn get_uid(req: &HttpRequest) -> Uuid {
 	*req.extensions().get().unwrap()
 }
 
+/// whether `order` has every university in `current` exactly once
+pub(super) fn is_reordering(order: &[i64], current: &[i64]) -> bool {
+	let mut order = order.to_vec();
+	order.sort_unstable();
+	let mut current = current.to_vec();
+	current.sort_unstable();
+
+	order == current
+}
+
 async fn get(con: web::Data<Pool>, req: HttpRequest) -> Result<impl Responder> {
 	let uid = get_uid(&req);
 
@@ -47,8 +79,44 @@ async fn get(con: web::Data<Pool>, req: HttpRequest) -> Result<impl Responder> {
 		.map(|user| HttpResponse::Ok().json(user))
 }
 
+/// fills in the coordinates of a home given by zip code, reusing the
+/// previous lookup if the zip code hasn't changed
+async fn resolve_home(
+	client: &Client,
+	home: Option<Home>,
+	previous: Option<&Home>,
+) -> Result<Option<Home>> {
+	let home = match home {
+		Some(home) => home,
+		None => return Ok(None),
+	};
+
+	let coords = match (home.latitude, home.longitude, home.zip.as_deref()) {
+		(_, _, Some(zip)) => match previous
+			.filter(|previous| previous.zip.as_deref() == Some(zip))
+			.and_then(Home::coordinates)
+		{
+			Some(coords) => coords,
+			None => locate_zip(client, zip).await?,
+		},
+		(Some(latitude), Some(longitude), None) => check_coordinates(latitude, longitude)?,
+		_ => {
+			return Err(ErrorBadRequest(
+				"home needs either latitude and longitude or zip",
+			))
+		}
+	};
+
+	Ok(Some(Home {
+		latitude: Some(coords.latitude),
+		longitude: Some(coords.longitude),
+		zip: home.zip,
+	}))
+}
+
 async fn update(
 	con: web::Data<Pool>,
+	client: web::Data<Client>,
 	req: HttpRequest,
 	metadata: web::Json<Metadata>,
 ) -> Result<impl Responder> {
@@ -76,7 +144,20 @@ async fn update(
 		}
 	}
 
-	user.metadata = metadata.0;
+	let mut metadata = metadata.0;
+	metadata.home = resolve_home(&client, metadata.home, user.metadata.home.as_ref()).await?;
+
+	// the home's saved weather is for the old coordinates
+	let coordinates = |home: &Option<Home>| {
+		home.as_ref()
+			.and_then(Home::coordinates)
+			.map(|coords| (coords.latitude, coords.longitude))
+	};
+	if coordinates(&metadata.home) != coordinates(&user.metadata.home) {
+		Location::delete_home(con.as_ref(), uid).await.into_500()?;
+	}
+
+	user.metadata = metadata;
 	user.update(con.as_ref())
 		.await
 		.map_err(ErrorInternalServerError)
@@ -109,6 +190,7 @@ fn create_jwt(uid: Uuid, key: &EncodingKey) -> Result<impl Responder> {
 
 async fn create(
 	con: web::Data<Pool>,
+	client: web::Data<Client>,
 	key: web::Data<EncodingKey>,
 	config: web::Data<argon2::Config<'static>>,
 	data: web::Json<Signup>,
@@ -132,9 +214,9 @@ async fn create(
 		}
 	})?;
 
-	let user = User::create(con.as_ref(), data.0.metadata)
-		.await
-		.map_err(ErrorInternalServerError)?;
+	let data = data.0;
+	let mut metadata = data.metadata;
+	metadata.home = resolve_home(&client, metadata.home, None).await?;
 
 	let mut salt = [0u8; 16];
 
@@ -142,7 +224,14 @@ async fn create(
 
 	let salt = salt.to_vec();
 
-	let hash = argon2::hash_raw(data.0.password.as_bytes(), &salt, &config)
+	let hash = argon2::hash_raw(data.password.as_bytes(), &salt, &config)
+		.map_err(ErrorInternalServerError)?;
+
+	// a user without a default list or a password would be stuck
+	let mut trans = con.begin().await.map_err(ErrorInternalServerError)?;
+
+	let user = User::create(&mut trans, metadata)
+		.await
 		.map_err(ErrorInternalServerError)?;
 
 	Authentication {
@@ -150,10 +239,12 @@ async fn create(
 		hash,
 		salt,
 	}
-	.put(con.as_ref())
+	.put(&mut trans)
 	.await
 	.map_err(ErrorInternalServerError)?;
 
+	trans.commit().await.map_err(ErrorInternalServerError)?;
+
 	create_jwt(user.id, &key)
 }
 
@@ -205,7 +296,21 @@ struct UpdateUniv {
 	id: i64,
 }
 
-async fn get_universities(con: web::Data<Pool>, req: HttpRequest) -> Result<impl Responder> {
+/// a tracked university, with how it fits the user's admissions profile
+#[derive(Debug, Serialize)]
+struct TrackedUniversity {
+	#[serde(flatten)]
+	university: University,
+	admissions: AdmissionsSummary,
+	classification: Classification,
+	tracking: Tracking,
+}
+
+async fn get_universities(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	filter: web::Query<TrackingFilter>,
+) -> Result<impl Responder> {
 	let uid = get_uid(&req);
 
 	let user = User::load(con.as_ref(), uid)
@@ -213,15 +318,59 @@ async fn get_universities(con: web::Data<Pool>, req: HttpRequest) -> Result<impl
 		.map_err(ErrorInternalServerError)?
 		.ok_or_else(|| ErrorUnauthorized("user not found"))?;
 
-	let universities = user
+	let profile = AdmissionsProfile::load(con.as_ref(), uid)
+		.await
+		.map_err(ErrorInternalServerError)?;
+
+	let mut tracking = Tracking::load_all(con.as_ref(), uid)
+		.await
+		.map_err(ErrorInternalServerError)?;
+
+	let universities: Vec<_> = user
 		.load_universities(con.as_ref())
 		.await
 		.map_err(ErrorInternalServerError)?
-		.ok_or_else(|| ErrorInternalServerError("university not found"))?;
+		.ok_or_else(|| ErrorInternalServerError("university not found"))?
+		.into_iter()
+		.map(|university| TrackedUniversity {
+			admissions: AdmissionsSummary::new(&university),
+			classification: Classification::new(profile.as_ref(), &university),
+			tracking: tracking.remove(&university.id).unwrap_or_default(),
+			university,
+		})
+		.filter(|tracked| filter.matches(&tracked.tracking))
+		.collect();
 
 	Ok(HttpResponse::Ok().json(universities))
 }
 
+async fn get_profile(con: web::Data<Pool>, req: HttpRequest) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	let profile = AdmissionsProfile::load(con.as_ref(), uid)
+		.await
+		.map_err(ErrorInternalServerError)?
+		.unwrap_or_default();
+
+	Ok(HttpResponse::Ok().json(profile))
+}
+
+async fn update_profile(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	profile: web::Json<AdmissionsProfile>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	profile.validate().map_err(ErrorBadRequest)?;
+
+	profile
+		.put(con.as_ref(), uid)
+		.await
+		.map_err(ErrorInternalServerError)
+		.map(|_| HttpResponse::Ok().body(()))
+}
+
 async fn add_university(
 	con: web::Data<Pool>,
 	req: HttpRequest,
@@ -229,18 +378,12 @@ async fn add_university(
 ) -> Result<impl Responder> {
 	let uid = get_uid(&req);
 
-	// TODO: check if user exists and hasn't already subscribed to university
-	// and university exists
+	// TODO: check if user exists and university exists
 
-	sqlx::query!(
-		"INSERT INTO get_weather (university_id, user_id) VALUES ($1, $2)",
-		data.id,
-		uid
-	)
-	.execute(con.as_ref())
-	.await
-	.map_err(ErrorInternalServerError)
-	.map(|x| HttpResponse::Ok().body(format!("{:?}", x)))
+	Tracking::track(con.as_ref(), uid, data.id)
+		.await
+		.map_err(ErrorInternalServerError)
+		.map(|_| HttpResponse::Ok().body(()))
 }
 
 async fn delete_university(
@@ -250,15 +393,392 @@ async fn delete_university(
 ) -> Result<impl Responder> {
 	let uid = get_uid(&req);
 
+	Tracking::untrack(con.as_ref(), uid, data.id)
+		.await
+		.map_err(ErrorInternalServerError)
+		.map(|_| HttpResponse::Ok().body(()))
+}
+
+async fn update_tracking(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+	data: web::Json<TrackingData>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+	let university_id = path.into_inner();
+	let mut data = data.0;
+
+	data.validate().map_err(ErrorBadRequest)?;
+
+	if Tracking::put(con.as_ref(), uid, university_id, &data)
+		.await
+		.map_err(ErrorInternalServerError)?
+	{
+		Ok(HttpResponse::Ok().body(()))
+	} else {
+		Err(ErrorNotFound("university not tracked"))
+	}
+}
+
+/// sorts the tracked universities in the order given, which must include all of them
+async fn reorder_universities(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	ids: web::Json<Vec<i64>>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	let user = User::load(con.as_ref(), uid)
+		.await
+		.map_err(ErrorInternalServerError)?
+		.ok_or_else(|| ErrorUnauthorized("user not found"))?;
+
+	if !is_reordering(&ids, &user.universities) {
+		return Err(ErrorBadRequest(
+			"order must list every tracked university exactly once",
+		));
+	}
+
+	// also puts the default list in the same order
+	Tracking::reorder(con.as_ref(), uid, &ids)
+		.await
+		.map_err(ErrorInternalServerError)?;
+
+	Ok(HttpResponse::Ok().body(()))
+}
+
+async fn get_tags(con: web::Data<Pool>, req: HttpRequest) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	Tracking::tags(con.as_ref(), uid)
+		.await
+		.map_err(ErrorInternalServerError)
+		.map(|tags| HttpResponse::Ok().json(tags))
+}
+
+/// deadlines can only be kept for universities the user is tracking
+async fn check_tracked(con: &Pool, uid: Uuid, university_id: i64) -> Result<()> {
 	sqlx::query!(
-		"DELETE FROM get_weather WHERE (university_id, user_id) = ($1, $2)",
-		data.id,
+		"SELECT university_id FROM get_weather WHERE university_id = $1 AND user_id = $2",
+		university_id,
 		uid
 	)
-	.execute(con.as_ref())
+	.fetch_optional(con)
+	.await
+	.map_err(ErrorInternalServerError)?
+	.map(|_| ())
+	.ok_or_else(|| ErrorNotFound("university not tracked"))
+}
+
+async fn get_deadlines(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+	let university_id = path.into_inner();
+
+	check_tracked(con.as_ref(), uid, university_id).await?;
+
+	Deadline::load_all(con.as_ref(), uid, university_id)
+		.await
+		.map_err(ErrorInternalServerError)
+		.map(|deadlines| HttpResponse::Ok().json(deadlines))
+}
+
+async fn add_deadline(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+	data: web::Json<DeadlineData>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+	let university_id = path.into_inner();
+
+	data.validate().map_err(ErrorBadRequest)?;
+	check_tracked(con.as_ref(), uid, university_id).await?;
+
+	Deadline::create(con.as_ref(), uid, university_id, data.0)
+		.await
+		.map_err(ErrorInternalServerError)
+		.map(|deadline| HttpResponse::Created().json(deadline))
+}
+
+async fn update_deadline(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<(i64, i64)>,
+	data: web::Json<DeadlineData>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+	let (university_id, id) = path.into_inner();
+
+	data.validate().map_err(ErrorBadRequest)?;
+
+	Deadline::update(con.as_ref(), uid, university_id, id, data.0)
+		.await
+		.map_err(ErrorInternalServerError)?
+		.ok_or_else(|| ErrorNotFound("deadline not found"))
+		.map(|deadline| HttpResponse::Ok().json(deadline))
+}
+
+async fn delete_deadline(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<(i64, i64)>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+	let (university_id, id) = path.into_inner();
+
+	if Deadline::delete(con.as_ref(), uid, university_id, id)
+		.await
+		.map_err(ErrorInternalServerError)?
+	{
+		Ok(HttpResponse::Ok().body(()))
+	} else {
+		Err(ErrorNotFound("deadline not found"))
+	}
+}
+
+async fn get_checklist(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+	let university_id = path.into_inner();
+
+	check_tracked(con.as_ref(), uid, university_id).await?;
+
+	ChecklistItem::load_all(con.as_ref(), uid, university_id)
+		.await
+		.map_err(ErrorInternalServerError)
+		.map(|items| HttpResponse::Ok().json(items))
+}
+
+#[derive(Debug, Deserialize)]
+struct NewItem {
+	label: String,
+}
+
+async fn add_checklist_item(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<i64>,
+	data: web::Json<NewItem>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+	let university_id = path.into_inner();
+
+	if data.label.trim().is_empty() {
+		return Err(ErrorBadRequest("label cannot be empty"));
+	}
+
+	check_tracked(con.as_ref(), uid, university_id).await?;
+
+	ChecklistItem::create(con.as_ref(), uid, university_id, data.0.label)
+		.await
+		.map_err(ErrorInternalServerError)
+		.map(|item| HttpResponse::Created().json(item))
+}
+
+async fn update_checklist_item(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<(i64, i64)>,
+	data: web::Json<ItemUpdate>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+	let (university_id, id) = path.into_inner();
+
+	if data
+		.label
+		.as_deref()
+		.is_some_and(|label| label.trim().is_empty())
+	{
+		return Err(ErrorBadRequest("label cannot be empty"));
+	}
+
+	ChecklistItem::update(con.as_ref(), uid, university_id, id, data.0)
+		.await
+		.map_err(ErrorInternalServerError)?
+		.ok_or_else(|| ErrorNotFound("checklist item not found"))
+		.map(|item| HttpResponse::Ok().json(item))
+}
+
+async fn delete_checklist_item(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	path: web::Path<(i64, i64)>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+	let (university_id, id) = path.into_inner();
+
+	if ChecklistItem::delete(con.as_ref(), uid, university_id, id)
+		.await
+		.map_err(ErrorInternalServerError)?
+	{
+		Ok(HttpResponse::Ok().body(()))
+	} else {
+		Err(ErrorNotFound("checklist item not found"))
+	}
+}
+
+/// progress on the checklists of every tracked university
+async fn get_progress(con: web::Data<Pool>, req: HttpRequest) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	let user = User::load(con.as_ref(), uid)
+		.await
+		.map_err(ErrorInternalServerError)?
+		.ok_or_else(|| ErrorUnauthorized("user not found"))?;
+
+	let universities = user
+		.load_universities(con.as_ref())
+		.await
+		.map_err(ErrorInternalServerError)?
+		.ok_or_else(|| ErrorInternalServerError("university not found"))?;
+
+	let mut progress = Vec::with_capacity(universities.len());
+	for university in &universities {
+		let items = ChecklistItem::load_all(con.as_ref(), uid, university.id)
+			.await
+			.map_err(ErrorInternalServerError)?;
+		progress.push(UniversityProgress::new(university, items));
+	}
+
+	Ok(HttpResponse::Ok().json(Progress::from(progress)))
+}
+
+#[derive(Debug, Deserialize)]
+struct LocalTimeQuery {
+	/// overrides the user's timezone preference
+	timezone: Option<Timezone>,
+}
+
+/// the weather at each tracked university at the same wall-clock time as the user's
+async fn same_local_time(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	query: web::Query<LocalTimeQuery>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	let user = User::load(con.as_ref(), uid)
+		.await
+		.map_err(ErrorInternalServerError)?
+		.ok_or_else(|| ErrorUnauthorized("user not found"))?;
+
+	let tz = query
+		.timezone
+		.or(user.metadata.timezone)
+		.ok_or_else(|| ErrorBadRequest("no timezone given and no timezone preference set"))?;
+
+	let local = Utc::now().with_timezone(&*tz).naive_local();
+
+	let universities = user
+		.load_universities(con.as_ref())
+		.await
+		.map_err(ErrorInternalServerError)?
+		.ok_or_else(|| ErrorInternalServerError("university not found"))?;
+
+	future::try_join_all(
+		universities
+			.iter()
+			.map(|university| LocalTimeWeather::load(con.as_ref(), university, local)),
+	)
 	.await
 	.map_err(ErrorInternalServerError)
-	.map(|_| HttpResponse::Ok().body(()))
+	.map(|weather| HttpResponse::Ok().json(weather))
+}
+
+/// the user's home coordinates, or 400 if they haven't set a home
+fn home_coordinates(user: &User) -> Result<Coordinates> {
+	user.metadata
+		.home
+		.as_ref()
+		.and_then(Home::coordinates)
+		.ok_or_else(|| ErrorBadRequest("no home location set"))
+}
+
+/// how far and how different each tracked university is from the user's home
+async fn from_home(
+	con: web::Data<Pool>,
+	client: web::Data<Client>,
+	req: HttpRequest,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	let user = User::load(con.as_ref(), uid)
+		.await
+		.map_err(ErrorInternalServerError)?
+		.ok_or_else(|| ErrorUnauthorized("user not found"))?;
+
+	let home = home_coordinates(&user)?;
+	let home_tz = geo::timezone::resolve(&home);
+
+	let universities = user
+		.load_universities(con.as_ref())
+		.await
+		.map_err(ErrorInternalServerError)?
+		.ok_or_else(|| ErrorInternalServerError("university not found"))?;
+
+	let weather = future::try_join_all(
+		universities
+			.iter()
+			.map(|university| current_weather(&con, &client, university.id)),
+	)
+	.await?;
+
+	let home_location = Location::load_or_create_home(con.as_ref(), uid, &home, Timezone(home_tz))
+		.await
+		.into_500()?;
+
+	// like the universities', the weather at home being unavailable shouldn't hide the rest
+	let home_weather = location_weather(&con, &client, &home_location)
+		.await
+		.map_err(|err| log::warn!("Error loading weather at home for {}: {}", uid, err))
+		.ok();
+
+	Ok(HttpResponse::Ok().json(HomeComparison {
+		weather: home_weather.as_ref().map(Into::into),
+		universities: universities
+			.iter()
+			.zip(&weather)
+			.map(|(university, weather)| {
+				FromHome::new(&home, &home_tz, university, weather.as_ref())
+			})
+			.collect(),
+	}))
+}
+
+#[derive(Debug, Deserialize)]
+struct NearHomeParams {
+	#[serde(default = "NearParams::default_radius_km")]
+	radius_km: f64,
+	#[serde(default = "NearParams::default_limit")]
+	limit: usize,
+}
+
+/// universities near the user's home
+async fn near_home(
+	con: web::Data<Pool>,
+	req: HttpRequest,
+	query: web::Query<NearHomeParams>,
+) -> Result<impl Responder> {
+	let uid = get_uid(&req);
+
+	let user = User::load(con.as_ref(), uid)
+		.await
+		.into_500()?
+		.ok_or_else(|| ErrorUnauthorized("user not found"))?;
+
+	let home = home_coordinates(&user)?;
+
+	load_nearby(&con, &home, query.radius_km, query.limit)
+		.await
+		.map(|nearby| HttpResponse::Ok().json(nearby))
 }
 
 #[derive(Debug, Deserialize)]
@@ -321,11 +841,50 @@ pub(super) fn configure(cfg: &mut ServiceConfig) {
 						.route(web::get().to(get))
 						.route(web::put().to(update)),
 				)
+				.service(
+					web::resource("/profile")
+						.route(web::get().to(get_profile))
+						.route(web::put().to(update_profile)),
+				)
 				.service(
 					web::resource("/universities")
 						.route(web::get().to(get_universities))
 						.route(web::put().to(add_university))
 						.route(web::delete().to(delete_university)),
+				)
+				.service(
+					web::resource("/universities/same-time").route(web::get().to(same_local_time)),
+				)
+				.service(web::resource("/universities/home").route(web::get().to(from_home)))
+				.service(web::resource("/near").route(web::get().to(near_home)))
+				.service(web::resource("/checklist").route(web::get().to(get_progress)))
+				.service(web::resource("/tags").route(web::get().to(get_tags)))
+				.service(web::scope("/lists").configure(super::list::configure))
+				.configure(super::export::configure)
+				.service(web::scope("/locations").configure(super::location::configure))
+				.service(
+					web::resource("/universities/order").route(web::put().to(reorder_universities)),
+				)
+				.service(web::resource("/universities/{id}").route(web::put().to(update_tracking)))
+				.service(
+					web::resource("/universities/{id}/checklist")
+						.route(web::get().to(get_checklist))
+						.route(web::post().to(add_checklist_item)),
+				)
+				.service(
+					web::resource("/universities/{id}/checklist/{item_id}")
+						.route(web::put().to(update_checklist_item))
+						.route(web::delete().to(delete_checklist_item)),
+				)
+				.service(
+					web::resource("/universities/{id}/deadlines")
+						.route(web::get().to(get_deadlines))
+						.route(web::post().to(add_deadline)),
+				)
+				.service(
+					web::resource("/universities/{id}/deadlines/{deadline_id}")
+						.route(web::put().to(update_deadline))
+						.route(web::delete().to(delete_deadline)),
 				),
 		);
 }
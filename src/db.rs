@@ -4,16 +4,20 @@ use sqlx::pool::PoolOptions;
 compile_error!("Only one database is supported.");
 
 #[cfg(feature = "sqlite")]
-type DB = sqlx::Sqlite;
+pub type DB = sqlx::Sqlite;
 
 #[cfg(feature = "postgres")]
-type DB = sqlx::Postgres;
+pub type DB = sqlx::Postgres;
 
 pub type Pool = sqlx::Pool<DB>;
 
 pub trait Executor<'a>: sqlx::Executor<'a, Database = DB> {}
 impl<'a, T: sqlx::Executor<'a, Database = DB>> Executor<'a> for T {}
 
+/// a pool or connection that can begin a transaction (or a savepoint, in a transaction)
+pub trait Acquire<'a>: sqlx::Acquire<'a, Database = DB> {}
+impl<'a, T: sqlx::Acquire<'a, Database = DB>> Acquire<'a> for T {}
+
 pub async fn create(url: &str) -> sqlx::Result<Pool> {
 	PoolOptions::new().connect(url).await
 }
@@ -0,0 +1,57 @@
+//! distances on the surface of the Earth
+
+use openweather_api::Coordinates;
+
+/// mean radius of the Earth
+pub const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// great-circle distance between two points, using the haversine formula
+pub fn haversine_km(a: &Coordinates, b: &Coordinates) -> f64 {
+	let (lat_a, lat_b) = (a.latitude.to_radians(), b.latitude.to_radians());
+	let d_lat = lat_b - lat_a;
+	let d_lon = (b.longitude - a.longitude).to_radians();
+
+	let h = (d_lat / 2.0).sin().powi(2) + lat_a.cos() * lat_b.cos() * (d_lon / 2.0).sin().powi(2);
+
+	2.0 * EARTH_RADIUS_KM * h.sqrt().asin()
+}
+
+/// latitude and longitude ranges that contain every point within `radius_km` of `center`
+#[derive(Debug)]
+pub struct BoundingBox {
+	pub min_latitude: f64,
+	pub max_latitude: f64,
+	pub min_longitude: f64,
+	pub max_longitude: f64,
+}
+
+impl BoundingBox {
+	pub fn around(center: &Coordinates, radius_km: f64) -> Self {
+		let d_lat = (radius_km / EARTH_RADIUS_KM).to_degrees();
+		let min_latitude = center.latitude - d_lat;
+		let max_latitude = center.latitude + d_lat;
+
+		// a degree of longitude gets shorter away from the equator, so this is widest at the
+		// latitude furthest from it. near a pole or across the antimeridian, check every longitude
+		let widest = min_latitude.abs().max(max_latitude.abs());
+		let d_lon = if widest < 90.0 {
+			d_lat / widest.to_radians().cos()
+		} else {
+			180.0
+		};
+
+		let (min_longitude, max_longitude) =
+			if center.longitude - d_lon < -180.0 || center.longitude + d_lon > 180.0 {
+				(-180.0, 180.0)
+			} else {
+				(center.longitude - d_lon, center.longitude + d_lon)
+			};
+
+		Self {
+			min_latitude: min_latitude.max(-90.0),
+			max_latitude: max_latitude.min(90.0),
+			min_longitude,
+			max_longitude,
+		}
+	}
+}
@@ -0,0 +1,38 @@
+//! offline timezone lookup, using the
+//! [timezone-boundary-builder](https://github.com/evansiroky/timezone-boundary-builder)
+//! data embedded by `tzf-rs`
+
+use chrono_tz::Tz;
+use openweather_api::Coordinates;
+use tzf_rs::DefaultFinder;
+
+lazy_static! {
+	// loading the boundaries takes a while, so only do it once
+	static ref FINDER: DefaultFinder = DefaultFinder::new();
+}
+
+/// the nautical timezone for a longitude, for places outside of every boundary (e.g., at sea)
+fn nautical(longitude: f64) -> Tz {
+	let offset = (longitude / 15.0).round() as i32;
+
+	// the signs in `Etc/GMT` names are inverted: `Etc/GMT+8` is UTC-8
+	let name = match offset {
+		0 => "Etc/GMT".to_owned(),
+		offset if offset > 0 => format!("Etc/GMT-{}", offset),
+		offset => format!("Etc/GMT+{}", -offset),
+	};
+
+	// offsets are within [-12, 12] for every valid longitude, and those are all in the database
+	name.parse().unwrap_or(Tz::UTC)
+}
+
+/// resolves the IANA timezone at a location.
+/// always returns a timezone, falling back to the nautical timezone if the location isn't
+/// in any boundary, or if `chrono_tz` doesn't know the zone (the boundaries can be newer).
+pub fn resolve(coords: &Coordinates) -> Tz {
+	FINDER
+		.get_tz_names(coords.longitude, coords.latitude)
+		.into_iter()
+		.find_map(|name| name.parse::<Tz>().ok())
+		.unwrap_or_else(|| nautical(coords.longitude))
+}
@@ -0,0 +1,453 @@
+//! imports universities from a CSV exported from the
+//! [IPEDS data center](https://nces.ed.gov/ipeds/datacenter/CDSPreview.aspx).
+//! see `dataset/doc.md` for which variables to select.
+
+use anyhow::Context;
+use csv::StringRecord;
+use openweather_api::Coordinates;
+
+use crate::{
+	db::Pool,
+	geo,
+	models::{
+		checklist::ChecklistItem, search, stats::Stats, timezone::Timezone,
+		university::Consideration,
+	},
+};
+
+use super::{Headers, Rejected, Report, Row};
+
+macro_rules! columns {
+	{ $($field:ident: $header:literal,)* } => {
+		/// indices of the columns used, by field
+		struct Columns {
+			$($field: usize,)*
+		}
+
+		impl Columns {
+			/// IPEDS prefixes most columns with the survey and year (e.g., `HD2020.`),
+			/// so `{y}` is replaced with the year and `{yy}` with the last 2 digits of the next year
+			fn new(headers: &Headers, year: i32) -> anyhow::Result<Self> {
+				let y = year.to_string();
+				let yy = format!("{:02}", (year + 1) % 100);
+
+				Ok(Self {
+					$($field: headers.index(&$header.replace("{yy}", &yy).replace("{y}", &y))?,)*
+				})
+			}
+		}
+	};
+}
+
+columns! {
+	unitid: "unitid",
+	name: "institution name",
+	aliases: "HD{y}.Institution name alias",
+	street_address: "HD{y}.Street address or post office box",
+	city: "HD{y}.City location of institution",
+	state: "HD{y}.State abbreviation",
+	zip_code: "HD{y}.ZIP code",
+	website: "HD{y}.Institution's internet website address",
+	admissions_website: "HD{y}.Admissions office web address",
+	longitude: "HD{y}.Longitude location of institution",
+	latitude: "HD{y}.Latitude location of institution",
+	opeid: "HD{y}.Office of Postsecondary Education (OPE) ID Number",
+	// the double space is in the original
+	total_enrollment: "DRVEF{y}.Total  enrollment",
+	undergrad_enrollment: "DRVEF{y}.Undergraduate enrollment",
+	student_to_faculty: "EF{y}D.Student-to-faculty ratio",
+	graduation_rate: "DRVGR{y}.Graduation rate, total cohort",
+	open_admission: "IC{y}.Open admission policy",
+	considers_gpa: "ADM{y}.Secondary school GPA",
+	considers_class_rank: "ADM{y}.Secondary school rank",
+	considers_transcript: "ADM{y}.Secondary school record",
+	considers_recommendations: "ADM{y}.Recommendations",
+	considers_test_scores: "ADM{y}.Admission test scores",
+	// (sic)
+	considers_toefl: "ADM{y}.TOEFL (Test of English as a Foreign Language",
+	total_applicants: "ADM{y}.Applicants total",
+	total_admissions: "ADM{y}.Admissions total",
+	total_enrolled_applicants: "ADM{y}.Enrolled total",
+	admissions_yield: "DRVADM{y}.Admissions yield - total",
+	submitted_sat: "ADM{y}.Percent of first-time degree/certificate-seeking students submitting SAT scores",
+	submitted_act: "ADM{y}.Percent of first-time degree/certificate-seeking students submitting ACT scores",
+	sat_english_1q: "ADM{y}.SAT Evidence-Based Reading and Writing 25th percentile score",
+	sat_english_3q: "ADM{y}.SAT Evidence-Based Reading and Writing 75th percentile score",
+	sat_math_1q: "ADM{y}.SAT Math 25th percentile score",
+	sat_math_3q: "ADM{y}.SAT Math 75th percentile score",
+	act_composite_1q: "ADM{y}.ACT Composite 25th percentile score",
+	act_composite_3q: "ADM{y}.ACT Composite 75th percentile score",
+	act_english_1q: "ADM{y}.ACT English 25th percentile score",
+	act_english_3q: "ADM{y}.ACT English 75th percentile score",
+	act_math_1q: "ADM{y}.ACT Math 25th percentile score",
+	act_math_3q: "ADM{y}.ACT Math 75th percentile score",
+	application_fee: "IC{y}.Undergraduate application fee",
+	price_in_district: "DRVIC{y}.Total price for in-district students living on campus  {y}-{yy}",
+	price_in_state: "DRVIC{y}.Total price for in-state students living on campus {y}-{yy}",
+	price_out_of_state: "DRVIC{y}.Total price for out-of-state students living on campus {y}-{yy}",
+}
+
+#[derive(Debug)]
+struct Record {
+	unitid: i64,
+	name: String,
+	acronym: String,
+	aliases: Option<String>,
+	street_address: String,
+	city: String,
+	state: String,
+	zip_code: String,
+	website: String,
+	admissions_website: Option<String>,
+	longitude: f64,
+	latitude: f64,
+	opeid: Option<String>,
+	total_enrollment: Option<i64>,
+	undergrad_enrollment: Option<i64>,
+	student_to_faculty: Option<i64>,
+	graduation_rate: Option<i64>,
+	open_admission: Option<bool>,
+	considers_gpa: Consideration,
+	considers_class_rank: Consideration,
+	considers_transcript: Consideration,
+	considers_recommendations: Consideration,
+	considers_test_scores: Consideration,
+	considers_toefl: Consideration,
+	total_applicants: Option<i64>,
+	total_admissions: Option<i64>,
+	total_enrolled_applicants: Option<i64>,
+	admissions_yield: Option<i64>,
+	submitted_sat: Option<i64>,
+	submitted_act: Option<i64>,
+	sat_english_1q: Option<i64>,
+	sat_english_3q: Option<i64>,
+	sat_math_1q: Option<i64>,
+	sat_math_3q: Option<i64>,
+	act_composite_1q: Option<i64>,
+	act_composite_3q: Option<i64>,
+	act_english_1q: Option<i64>,
+	act_english_3q: Option<i64>,
+	act_math_1q: Option<i64>,
+	act_math_3q: Option<i64>,
+	application_fee: Option<i64>,
+	price_in_district: Option<i64>,
+	price_in_state: Option<i64>,
+	price_out_of_state: Option<i64>,
+	timezone: Timezone,
+}
+
+fn consideration(row: &Row, idx: usize, name: &str) -> Result<Consideration, String> {
+	match row.get(idx) {
+		None => Ok(Consideration::Unspecified),
+		Some("Required") => Ok(Consideration::Required),
+		Some("Recommended") => Ok(Consideration::Recommended),
+		Some("Considered but not required") => Ok(Consideration::Considered),
+		Some("Neither required nor recommended") => Ok(Consideration::NotRecommended),
+		Some(other) => Err(format!("{} ({:?}) is not a consideration", name, other)),
+	}
+}
+
+fn yes_no(row: &Row, idx: usize, name: &str) -> Result<Option<bool>, String> {
+	match row.get(idx) {
+		None | Some("Not applicable") => Ok(None),
+		Some("Yes") => Ok(Some(true)),
+		Some("No") => Ok(Some(false)),
+		Some(other) => Err(format!("{} ({:?}) is not yes/no", name, other)),
+	}
+}
+
+/// OPE ids are 8 digits, but spreadsheets tend to strip the leading zeros.
+/// IPEDS uses `-2` for "not applicable"
+fn opeid(row: &Row, idx: usize) -> Result<Option<String>, String> {
+	match row.get(idx) {
+		None | Some("-2") => Ok(None),
+		Some(id) if id.len() <= 8 && id.bytes().all(|b| b.is_ascii_digit()) => {
+			Ok(Some(format!("{:0>8}", id)))
+		}
+		Some(other) => Err(format!("OPE id ({:?}) is invalid", other)),
+	}
+}
+
+impl Record {
+	fn parse(c: &Columns, record: &StringRecord) -> Result<Self, String> {
+		let row = Row { record };
+
+		let string = |idx, name| row.required(idx, name).map(str::to_owned);
+		let optional = |idx| row.get(idx).map(str::to_owned);
+		let int = |idx, name| row.parse::<i64>(idx, name);
+
+		let latitude = row
+			.parse::<f64>(c.latitude, "latitude")?
+			.ok_or("latitude is missing")?;
+		let longitude = row
+			.parse::<f64>(c.longitude, "longitude")?
+			.ok_or("longitude is missing")?;
+
+		if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+			return Err(format!(
+				"({}, {}) is not a valid location",
+				latitude, longitude
+			));
+		}
+
+		let name = string(c.name, "name")?;
+
+		Ok(Self {
+			unitid: int(c.unitid, "unitid")?.ok_or("unitid is missing")?,
+			acronym: search::acronym(&name),
+			name,
+			aliases: optional(c.aliases),
+			street_address: string(c.street_address, "street address")?,
+			city: string(c.city, "city")?,
+			state: string(c.state, "state")?,
+			zip_code: string(c.zip_code, "ZIP code")?,
+			website: string(c.website, "website")?,
+			admissions_website: optional(c.admissions_website),
+			longitude,
+			latitude,
+			opeid: opeid(&row, c.opeid)?,
+			total_enrollment: int(c.total_enrollment, "total enrollment")?,
+			undergrad_enrollment: int(c.undergrad_enrollment, "undergraduate enrollment")?,
+			student_to_faculty: int(c.student_to_faculty, "student-to-faculty ratio")?,
+			graduation_rate: int(c.graduation_rate, "graduation rate")?,
+			open_admission: yes_no(&row, c.open_admission, "open admission")?,
+			considers_gpa: consideration(&row, c.considers_gpa, "GPA")?,
+			considers_class_rank: consideration(&row, c.considers_class_rank, "class rank")?,
+			considers_transcript: consideration(&row, c.considers_transcript, "transcript")?,
+			considers_recommendations: consideration(
+				&row,
+				c.considers_recommendations,
+				"recommendations",
+			)?,
+			considers_test_scores: consideration(&row, c.considers_test_scores, "test scores")?,
+			considers_toefl: consideration(&row, c.considers_toefl, "TOEFL")?,
+			total_applicants: int(c.total_applicants, "total applicants")?,
+			total_admissions: int(c.total_admissions, "total admissions")?,
+			total_enrolled_applicants: int(c.total_enrolled_applicants, "total enrolled")?,
+			admissions_yield: int(c.admissions_yield, "admissions yield")?,
+			submitted_sat: int(c.submitted_sat, "percent submitting SAT")?,
+			submitted_act: int(c.submitted_act, "percent submitting ACT")?,
+			sat_english_1q: int(c.sat_english_1q, "SAT English 25th percentile")?,
+			sat_english_3q: int(c.sat_english_3q, "SAT English 75th percentile")?,
+			sat_math_1q: int(c.sat_math_1q, "SAT math 25th percentile")?,
+			sat_math_3q: int(c.sat_math_3q, "SAT math 75th percentile")?,
+			act_composite_1q: int(c.act_composite_1q, "ACT composite 25th percentile")?,
+			act_composite_3q: int(c.act_composite_3q, "ACT composite 75th percentile")?,
+			act_english_1q: int(c.act_english_1q, "ACT English 25th percentile")?,
+			act_english_3q: int(c.act_english_3q, "ACT English 75th percentile")?,
+			act_math_1q: int(c.act_math_1q, "ACT math 25th percentile")?,
+			act_math_3q: int(c.act_math_3q, "ACT math 75th percentile")?,
+			application_fee: int(c.application_fee, "application fee")?,
+			price_in_district: int(c.price_in_district, "in-district price")?,
+			price_in_state: int(c.price_in_state, "in-state price")?,
+			price_out_of_state: int(c.price_out_of_state, "out-of-state price")?,
+			timezone: Timezone(geo::timezone::resolve(&Coordinates {
+				latitude,
+				longitude,
+			})),
+		})
+	}
+
+	/// saves the university's directory information and its statistics for `year`
+	async fn upsert(
+		&self,
+		con: &mut sqlx::Transaction<'_, crate::db::DB>,
+		year: i32,
+	) -> sqlx::Result<()> {
+		let year = i64::from(year);
+
+		// universities imported by migrate.py don't have a unit id yet
+		sqlx::query!(
+			"UPDATE universities SET unitid = $1
+			WHERE unitid IS NULL AND name = $2 AND city = $3 AND state = $4",
+			self.unitid,
+			self.name,
+			self.city,
+			self.state
+		)
+		.execute(&mut *con)
+		.await?;
+
+		sqlx::query!(
+			"INSERT INTO universities (
+				unitid,
+				name,
+				acronym,
+				aliases,
+				street_address,
+				city,
+				state,
+				zip_code,
+				website,
+				admissions_website,
+				longitude,
+				latitude,
+				opeid,
+				open_admission,
+				considers_gpa,
+				considers_class_rank,
+				considers_transcript,
+				considers_recommendations,
+				considers_test_scores,
+				considers_toefl,
+				timezone
+			) VALUES (
+				$1, $2, $3, $4, $5, $6, $7, $8, $9, $10,
+				$11, $12, $13, $14, $15, $16, $17, $18, $19, $20,
+				$21
+			) ON CONFLICT(unitid) DO UPDATE SET (
+				name,
+				acronym,
+				aliases,
+				street_address,
+				city,
+				state,
+				zip_code,
+				website,
+				admissions_website,
+				longitude,
+				latitude,
+				opeid,
+				open_admission,
+				considers_gpa,
+				considers_class_rank,
+				considers_transcript,
+				considers_recommendations,
+				considers_test_scores,
+				considers_toefl,
+				timezone
+			) = (
+				excluded.name,
+				excluded.acronym,
+				excluded.aliases,
+				excluded.street_address,
+				excluded.city,
+				excluded.state,
+				excluded.zip_code,
+				excluded.website,
+				excluded.admissions_website,
+				excluded.longitude,
+				excluded.latitude,
+				excluded.opeid,
+				excluded.open_admission,
+				excluded.considers_gpa,
+				excluded.considers_class_rank,
+				excluded.considers_transcript,
+				excluded.considers_recommendations,
+				excluded.considers_test_scores,
+				excluded.considers_toefl,
+				excluded.timezone
+			)
+			-- importing an older year shouldn't overwrite newer directory information
+			WHERE NOT EXISTS (
+				SELECT 1 FROM university_stats WHERE university_id = universities.id AND year > $22
+			)",
+			self.unitid,
+			self.name,
+			self.acronym,
+			self.aliases,
+			self.street_address,
+			self.city,
+			self.state,
+			self.zip_code,
+			self.website,
+			self.admissions_website,
+			self.longitude,
+			self.latitude,
+			self.opeid,
+			self.open_admission,
+			self.considers_gpa,
+			self.considers_class_rank,
+			self.considers_transcript,
+			self.considers_recommendations,
+			self.considers_test_scores,
+			self.considers_toefl,
+			self.timezone,
+			year
+		)
+		.execute(&mut *con)
+		.await?;
+
+		let university_id =
+			sqlx::query!("SELECT id FROM universities WHERE unitid = $1", self.unitid)
+				.fetch_one(&mut *con)
+				.await?
+				.id;
+
+		Stats {
+			university_id,
+			year,
+			total_enrollment: self.total_enrollment,
+			undergrad_enrollment: self.undergrad_enrollment,
+			student_to_faculty: self.student_to_faculty,
+			graduation_rate: self.graduation_rate,
+			total_applicants: self.total_applicants,
+			total_admissions: self.total_admissions,
+			total_enrolled_applicants: self.total_enrolled_applicants,
+			admissions_yield: self.admissions_yield,
+			submitted_sat: self.submitted_sat,
+			submitted_act: self.submitted_act,
+			sat_english_1q: self.sat_english_1q,
+			sat_english_3q: self.sat_english_3q,
+			sat_math_1q: self.sat_math_1q,
+			sat_math_3q: self.sat_math_3q,
+			act_composite_1q: self.act_composite_1q,
+			act_composite_3q: self.act_composite_3q,
+			act_english_1q: self.act_english_1q,
+			act_english_3q: self.act_english_3q,
+			act_math_1q: self.act_math_1q,
+			act_math_3q: self.act_math_3q,
+			application_fee: self.application_fee,
+			price_in_district: self.price_in_district,
+			price_in_state: self.price_in_state,
+			price_out_of_state: self.price_out_of_state,
+		}
+		.put(&mut *con)
+		.await
+	}
+}
+
+/// imports (or updates) every university in the CSV at `path`, with data from `year`
+pub async fn import(con: &Pool, path: &str, year: i32) -> anyhow::Result<Report> {
+	let mut reader = super::open(path)?;
+
+	let headers = Headers::new(reader.headers().context("Error reading CSV headers")?);
+	let columns = Columns::new(&headers, year)?;
+
+	let mut trans = con.begin().await.context("Error beginning transaction")?;
+	let mut report = Report::default();
+
+	for (row, result) in (2..).zip(reader.records()) {
+		let record = result.context("Error reading CSV")?;
+
+		let university = match Record::parse(&columns, &record) {
+			Ok(university) => university,
+			Err(reason) => {
+				log::warn!("Rejected row {}: {}", row, reason);
+				report.rejected.push(Rejected { row, reason });
+				continue;
+			}
+		};
+
+		university.upsert(&mut trans, year).await.with_context(|| {
+			format!(
+				"Error saving {} (unitid {}) on row {}",
+				university.name, university.unitid, row
+			)
+		})?;
+
+		report.imported += 1;
+	}
+
+	trans
+		.commit()
+		.await
+		.context("Error committing transaction")?;
+
+	// requirements might have changed
+	ChecklistItem::generate_all(con)
+		.await
+		.context("Error updating checklists")?;
+
+	Ok(report)
+}
@@ -0,0 +1,106 @@
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use anyhow::{anyhow, Context};
+use csv::StringRecord;
+
+pub mod ipeds;
+pub mod scorecard;
+pub mod timezones;
+
+/// a CSV row that could not be imported
+#[derive(Debug)]
+pub struct Rejected {
+	/// row in the CSV file, counting the header as row 1 (like a spreadsheet)
+	pub row: u64,
+	pub reason: String,
+}
+
+#[derive(Debug, Default)]
+pub struct Report {
+	pub imported: u64,
+	/// rows that were valid, but not for anything in clime
+	pub skipped: u64,
+	pub rejected: Vec<Rejected>,
+}
+
+impl fmt::Display for Report {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "Imported {} rows, ", self.imported)?;
+
+		if self.skipped > 0 {
+			write!(f, "skipped {} rows, ", self.skipped)?;
+		}
+
+		writeln!(f, "rejected {} rows.", self.rejected.len())?;
+
+		for rejected in &self.rejected {
+			writeln!(f, "row {}: {}", rejected.row, rejected.reason)?;
+		}
+
+		Ok(())
+	}
+}
+
+/// looks up CSV fields by header name
+#[derive(Debug)]
+pub struct Headers(HashMap<String, usize>);
+
+impl Headers {
+	pub fn new(headers: &StringRecord) -> Self {
+		Self(
+			headers
+				.iter()
+				.enumerate()
+				.map(|(i, name)| (name.trim().to_owned(), i))
+				.collect(),
+		)
+	}
+
+	/// gets the index of a column, failing if the CSV doesn't have it
+	pub fn index(&self, name: &str) -> anyhow::Result<usize> {
+		self.0
+			.get(name)
+			.copied()
+			.ok_or_else(|| anyhow!("CSV is missing column {:?}", name))
+	}
+}
+
+/// a single CSV row, with errors that mention which column was wrong
+pub struct Row<'a> {
+	pub record: &'a StringRecord,
+}
+
+impl<'a> Row<'a> {
+	/// the trimmed field, or `None` if it is empty
+	pub fn get(&self, idx: usize) -> Option<&'a str> {
+		self.record
+			.get(idx)
+			.map(str::trim)
+			.filter(|field| !field.is_empty())
+	}
+
+	pub fn required(&self, idx: usize, name: &str) -> Result<&'a str, String> {
+		self.get(idx).ok_or_else(|| format!("{} is missing", name))
+	}
+
+	pub fn parse<T: FromStr>(&self, idx: usize, name: &str) -> Result<Option<T>, String>
+	where
+		T::Err: fmt::Display,
+	{
+		self.get(idx)
+			.map(|field| {
+				field
+					.parse()
+					.map_err(|e| format!("{} ({:?}) is invalid: {}", name, field, e))
+			})
+			.transpose()
+	}
+}
+
+/// opens a CSV file with headers
+pub fn open(path: &str) -> anyhow::Result<csv::Reader<std::fs::File>> {
+	csv::ReaderBuilder::new()
+		.flexible(true)
+		.from_path(path)
+		.with_context(|| format!("Error opening CSV file {}", path))
+}
@@ -0,0 +1,153 @@
+//! imports outcomes from the College Scorecard bulk data
+//! (`Most-Recent-Cohorts-Institution.csv` from <https://collegescorecard.ed.gov/data/>),
+//! for universities that have already been imported from IPEDS
+
+use std::{fmt, str::FromStr};
+
+use anyhow::Context;
+use csv::StringRecord;
+
+use crate::{
+	db::Pool,
+	models::{outcomes::Outcomes, university::University},
+};
+
+use super::{Headers, Rejected, Report, Row};
+
+/// the Scorecard has both public and private net prices, and only one is filled in
+struct NetPrice {
+	public: usize,
+	private: usize,
+}
+
+impl NetPrice {
+	fn new(headers: &Headers, bracket: u8) -> anyhow::Result<Self> {
+		Ok(Self {
+			public: headers.index(&format!("NPT4{}_PUB", bracket))?,
+			private: headers.index(&format!("NPT4{}_PRIV", bracket))?,
+		})
+	}
+}
+
+struct Columns {
+	unitid: usize,
+	median_earnings: usize,
+	median_debt: usize,
+	net_price: [NetPrice; 5],
+	completion_rate_4: usize,
+	completion_rate_less_than_4: usize,
+}
+
+impl Columns {
+	fn new(headers: &Headers) -> anyhow::Result<Self> {
+		Ok(Self {
+			unitid: headers.index("UNITID")?,
+			median_earnings: headers.index("MD_EARN_WNE_P10")?,
+			median_debt: headers.index("GRAD_DEBT_MDN")?,
+			net_price: [
+				NetPrice::new(headers, 1)?,
+				NetPrice::new(headers, 2)?,
+				NetPrice::new(headers, 3)?,
+				NetPrice::new(headers, 4)?,
+				NetPrice::new(headers, 5)?,
+			],
+			completion_rate_4: headers.index("C150_4")?,
+			completion_rate_less_than_4: headers.index("C150_L4")?,
+		})
+	}
+}
+
+/// like `Row::parse`, but the Scorecard uses `NULL` for missing data and `PrivacySuppressed`
+/// when there are too few students to report it
+fn field<T: FromStr>(row: &Row, idx: usize, name: &str) -> Result<Option<T>, String>
+where
+	T::Err: fmt::Display,
+{
+	match row.get(idx) {
+		None | Some("NULL") | Some("PrivacySuppressed") => Ok(None),
+		Some(_) => row.parse(idx, name),
+	}
+}
+
+fn net_price(row: &Row, columns: &NetPrice, name: &str) -> Result<Option<i64>, String> {
+	Ok(field::<f64>(row, columns.public, name)?
+		.or(field(row, columns.private, name)?)
+		.map(|price| price.round() as i64))
+}
+
+/// parses a row into its unit id and outcomes (with a placeholder university id)
+fn parse(c: &Columns, record: &StringRecord) -> Result<(i64, Outcomes), String> {
+	let row = Row { record };
+
+	let unitid = row.parse(c.unitid, "UNITID")?.ok_or("UNITID is missing")?;
+	let [p1, p2, p3, p4, p5] = &c.net_price;
+
+	let outcomes = Outcomes {
+		university_id: 0,
+		median_earnings: field::<f64>(&row, c.median_earnings, "median earnings")?
+			.map(|earnings| earnings.round() as i64),
+		median_debt: field(&row, c.median_debt, "median debt")?,
+		net_price_0_30000: net_price(&row, p1, "net price ($0-30,000)")?,
+		net_price_30001_48000: net_price(&row, p2, "net price ($30,001-48,000)")?,
+		net_price_48001_75000: net_price(&row, p3, "net price ($48,001-75,000)")?,
+		net_price_75001_110000: net_price(&row, p4, "net price ($75,001-110,000)")?,
+		net_price_110001_plus: net_price(&row, p5, "net price ($110,001+)")?,
+		completion_rate: field(&row, c.completion_rate_4, "completion rate")?.or(field(
+			&row,
+			c.completion_rate_less_than_4,
+			"completion rate (less than 4 years)",
+		)?),
+	};
+
+	Ok((unitid, outcomes))
+}
+
+/// imports the outcomes of every university in the CSV at `path`, matched by unit id.
+/// rows for universities that aren't in clime are skipped.
+pub async fn import(con: &Pool, path: &str) -> anyhow::Result<Report> {
+	let mut reader = super::open(path)?;
+
+	let headers = Headers::new(reader.headers().context("Error reading CSV headers")?);
+	let columns = Columns::new(&headers)?;
+
+	let mut trans = con.begin().await.context("Error beginning transaction")?;
+	let mut report = Report::default();
+
+	for (row, result) in (2..).zip(reader.records()) {
+		let record = result.context("Error reading CSV")?;
+
+		let (unitid, mut outcomes) = match parse(&columns, &record) {
+			Ok(parsed) => parsed,
+			Err(reason) => {
+				log::warn!("Rejected row {}: {}", row, reason);
+				report.rejected.push(Rejected { row, reason });
+				continue;
+			}
+		};
+
+		outcomes.university_id = match University::id_from_unitid(&mut trans, unitid)
+			.await
+			.with_context(|| format!("Error finding unitid {} on row {}", unitid, row))?
+		{
+			Some(id) => id,
+			None => {
+				report.skipped += 1;
+				continue;
+			}
+		};
+
+		outcomes
+			.put(&mut trans)
+			.await
+			.with_context(|| format!("Error saving unitid {} on row {}", unitid, row))?;
+
+		report.imported += 1;
+	}
+
+	trans
+		.commit()
+		.await
+		.context("Error committing transaction")?;
+
+	Ok(report)
+}
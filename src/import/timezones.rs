@@ -0,0 +1,56 @@
+//! (re)computes the timezone of every university from its location
+
+use anyhow::Context;
+use openweather_api::Coordinates;
+
+use crate::{db::Pool, geo, models::timezone::Timezone};
+
+/// updates every university whose stored timezone doesn't match its location,
+/// returning how many were changed
+pub async fn update(con: &Pool) -> anyhow::Result<u64> {
+	let universities = sqlx::query!(
+		r#"SELECT id, latitude, longitude, timezone AS "timezone: String" FROM universities"#
+	)
+	.fetch_all(con)
+	.await
+	.context("Error getting universities")?;
+
+	let mut trans = con.begin().await.context("Error beginning transaction")?;
+	let mut updated = 0;
+
+	for row in universities {
+		let tz = Timezone(geo::timezone::resolve(&Coordinates {
+			latitude: row.latitude,
+			longitude: row.longitude,
+		}));
+
+		if row.timezone == tz.name() {
+			continue;
+		}
+
+		log::info!(
+			"Updating timezone of university {} from {} to {}",
+			row.id,
+			row.timezone,
+			tz
+		);
+
+		sqlx::query!(
+			"UPDATE universities SET timezone = $1 WHERE id = $2",
+			tz,
+			row.id
+		)
+		.execute(&mut trans)
+		.await
+		.with_context(|| format!("Error updating university {}", row.id))?;
+
+		updated += 1;
+	}
+
+	trans
+		.commit()
+		.await
+		.context("Error committing transaction")?;
+
+	Ok(updated)
+}
@@ -1,17 +1,24 @@
 #[macro_use]
 extern crate lazy_static;
 
-use std::env;
+use std::{env, sync::Arc};
 
 use actix_files::{Files, NamedFile};
 use actix_web::{web, App, HttpServer};
-use anyhow::Context;
+use anyhow::{bail, Context};
 use jsonwebtoken::{DecodingKey, EncodingKey};
 use openweather_api::Client;
-use workers::{forecast::ForecastUpdater, weather::WeatherUpdater, Updater};
+use workers::{
+	forecast::ForecastUpdater,
+	reminders::{LogNotifier, ReminderUpdater},
+	weather::WeatherUpdater,
+	Updater,
+};
 
 mod api;
 pub mod db;
+mod geo;
+mod import;
 pub mod models;
 mod workers;
 
@@ -24,6 +31,11 @@ lazy_static! {
 	static ref PASSWORD_SECRET: String = get_env("PASSWORD_SECRET").unwrap();
 }
 
+const USAGE: &str = "usage: clime [serve | import-ipeds <csv> [--year <year>] | import-scorecard <csv> | update-timezones]";
+
+/// the year of the IPEDS data in `dataset/doc.md`
+const DEFAULT_IPEDS_YEAR: i32 = 2020;
+
 #[actix_web::main]
 async fn main() -> anyhow::Result<()> {
 	dotenv::dotenv().context("Error loading .env file.")?;
@@ -33,6 +45,58 @@ async fn main() -> anyhow::Result<()> {
 		.await
 		.context("Error connecting to database")?;
 
+	sqlx::migrate!("./migrations")
+		.run(&con)
+		.await
+		.context("Error running database migrations.")?;
+
+	models::search::backfill_acronyms(&con)
+		.await
+		.context("Error setting university acronyms.")?;
+
+	// checklists used to be generated when they were first loaded
+	models::checklist::ChecklistItem::generate_all(&con)
+		.await
+		.context("Error generating checklists.")?;
+
+	#[cfg(feature = "postgres")]
+	models::search::create_indices(&con)
+		.await
+		.context("Error creating university search indices.")?;
+
+	let mut args = env::args().skip(1);
+
+	match args.next().as_deref() {
+		None | Some("serve") => serve(con).await,
+		Some("import-ipeds") => {
+			let path = args.next().context(USAGE)?;
+			let year = match (args.next().as_deref(), args.next()) {
+				(None, _) => DEFAULT_IPEDS_YEAR,
+				(Some("--year"), Some(year)) => year.parse().context("year is not a number")?,
+				_ => bail!(USAGE),
+			};
+
+			let report = import::ipeds::import(&con, &path, year).await?;
+			print!("{}", report);
+			Ok(())
+		}
+		Some("import-scorecard") => {
+			let path = args.next().context(USAGE)?;
+
+			let report = import::scorecard::import(&con, &path).await?;
+			print!("{}", report);
+			Ok(())
+		}
+		Some("update-timezones") => {
+			let updated = import::timezones::update(&con).await?;
+			println!("Updated the timezone of {} universities.", updated);
+			Ok(())
+		}
+		Some(_) => bail!(USAGE),
+	}
+}
+
+async fn serve(con: db::Pool) -> anyhow::Result<()> {
 	let jwt_secret = get_env("JWT_SECRET")?;
 	let encoder = EncodingKey::from_secret(jwt_secret.as_bytes());
 	let decoder = DecodingKey::from_secret(jwt_secret.as_bytes());
@@ -54,10 +118,10 @@ async fn main() -> anyhow::Result<()> {
 		client: client.clone(),
 	});
 
-	sqlx::migrate!("./migrations")
-		.run(&con)
-		.await
-		.context("Error running database migrations.")?;
+	Updater::start(ReminderUpdater {
+		con: con.clone(),
+		notifier: Arc::new(LogNotifier),
+	});
 
 	HttpServer::new(move || {
 		App::new()
@@ -0,0 +1,135 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+use crate::db::Executor;
+
+use super::{
+	forecast::Forecast,
+	weather::{is_precipitation, Weather},
+};
+
+/// width of each lead-time bucket, in hours
+const BUCKET_HOURS: i64 = 24;
+
+/// the forecast API goes out 5 days
+const BUCKETS: usize = 5;
+
+/// forecasts are only compared against observations at most this far from the forecast time
+const MAX_OBSERVATION_MINUTES: i64 = 90;
+
+/// a forecast with at least this chance of precipitation counts as predicting precipitation
+const PRECIPITATION_THRESHOLD: f64 = 0.5;
+
+#[derive(Debug, Serialize)]
+pub struct LeadTimeAccuracy {
+	/// lower bound of the lead time (forecast time - time it was fetched), in hours
+	pub lead_hours_min: i64,
+	/// upper bound of the lead time, in hours (exclusive)
+	pub lead_hours_max: i64,
+	/// number of forecasts that could be matched to an observation
+	pub samples: u32,
+	/// mean absolute error of the forecast temperature, in kelvin (same as °C)
+	pub temperature_mae: Option<f64>,
+	/// proportion of forecasts that correctly predicted whether it would precipitate
+	pub precipitation_hit_rate: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Accuracy {
+	pub university_id: i64,
+	/// number of days of history the statistics were computed over
+	pub days: i64,
+	pub lead_times: Vec<LeadTimeAccuracy>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Bucket {
+	samples: u32,
+	temperature_error: f64,
+	precipitation_hits: u32,
+}
+
+/// finds the observation closest to `time`, if there is one close enough.
+/// `observations` must be sorted by time.
+fn closest_observation(observations: &[Weather], time: DateTime<Utc>) -> Option<&Weather> {
+	let idx = observations.partition_point(|obs| obs.time < time);
+
+	[idx.checked_sub(1), Some(idx)]
+		.into_iter()
+		.flatten()
+		.filter_map(|i| observations.get(i))
+		.map(|obs| (obs, (obs.time - time).num_seconds().abs()))
+		.filter(|(_, distance)| *distance <= MAX_OBSERVATION_MINUTES * 60)
+		.min_by_key(|(_, distance)| *distance)
+		.map(|(obs, _)| obs)
+}
+
+impl Accuracy {
+	/// compares every forecast revision for times in the last `days` days against the observed weather
+	pub async fn load(
+		con: impl Executor<'_> + Clone,
+		university_id: i64,
+		days: i64,
+	) -> sqlx::Result<Self> {
+		let end = Utc::now();
+		let start = end - Duration::days(days);
+
+		let forecasts =
+			Forecast::get_revisions_between(con.clone(), university_id, start, end).await?;
+
+		let observations = Weather::get_between(
+			con,
+			university_id,
+			start - Duration::minutes(MAX_OBSERVATION_MINUTES),
+			end,
+		)
+		.await?;
+
+		let mut buckets = [Bucket::default(); BUCKETS];
+
+		for forecast in forecasts {
+			let lead = (forecast.time - forecast.fetched_at).num_hours();
+			let bucket = match usize::try_from(lead / BUCKET_HOURS)
+				.ok()
+				.and_then(|i| buckets.get_mut(i))
+			{
+				Some(bucket) => bucket,
+				None => continue,
+			};
+
+			let observation = match closest_observation(&observations, forecast.time) {
+				Some(obs) => obs,
+				None => continue,
+			};
+
+			bucket.samples += 1;
+			bucket.temperature_error += (forecast.temperature - observation.temperature).abs();
+
+			let predicted = forecast.precipitation_chance >= PRECIPITATION_THRESHOLD;
+			if predicted == is_precipitation(observation.weather_type) {
+				bucket.precipitation_hits += 1;
+			}
+		}
+
+		let lead_times = buckets
+			.into_iter()
+			.zip(0..)
+			.map(|(bucket, i)| {
+				let samples = (bucket.samples > 0).then_some(bucket.samples as f64);
+				LeadTimeAccuracy {
+					lead_hours_min: i * BUCKET_HOURS,
+					lead_hours_max: (i + 1) * BUCKET_HOURS,
+					samples: bucket.samples,
+					temperature_mae: samples.map(|n| bucket.temperature_error / n),
+					precipitation_hit_rate: samples.map(|n| bucket.precipitation_hits as f64 / n),
+				}
+			})
+			.collect();
+
+		Ok(Self {
+			university_id,
+			days,
+			lead_times,
+		})
+	}
+}
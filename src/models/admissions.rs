@@ -0,0 +1,63 @@
+use serde::Serialize;
+
+use super::{stats::Range, university::University};
+
+/// admissions numbers computed from a university's raw statistics,
+/// so every client shows the same thing. anything that can't be computed is `null`
+#[derive(Debug, Serialize)]
+pub struct AdmissionsSummary {
+	/// percent of applicants who were admitted
+	pub acceptance_rate: Option<f64>,
+	/// percent of admitted students who enrolled
+	pub yield_rate: Option<f64>,
+	/// 25th to 75th percentile of total (reading and writing + math) SAT scores
+	pub sat_composite: Option<Range>,
+	/// 25th to 75th percentile of composite ACT scores
+	pub act_composite: Option<Range>,
+	/// out-of-state price minus in-state price (0 for most private universities)
+	pub price_gap: Option<i64>,
+}
+
+/// `part` as a percent of `whole`. `None` if either is missing, `whole` is 0,
+/// or `part` is bigger than `whole` (which would be a mistake in the data)
+pub fn percent(part: Option<i64>, whole: Option<i64>) -> Option<f64> {
+	match (part, whole) {
+		(Some(part), Some(whole)) if whole > 0 && (0..=whole).contains(&part) => {
+			Some(100.0 * part as f64 / whole as f64)
+		}
+		_ => None,
+	}
+}
+
+/// the total SAT range, only if both sections were reported
+pub fn sat_composite(english: Option<&Range>, math: Option<&Range>) -> Option<Range> {
+	let (english, math) = (english?, math?);
+	Some(Range {
+		low: english.low + math.low,
+		high: english.high + math.high,
+	})
+}
+
+impl AdmissionsSummary {
+	pub fn new(university: &University) -> Self {
+		let u = university;
+
+		Self {
+			acceptance_rate: percent(u.total_admissions, u.total_applicants),
+			// IPEDS reports yield for most universities, but it can be computed otherwise
+			yield_rate: u
+				.admissions_yield
+				.map(|rate| rate as f64)
+				.or_else(|| percent(u.total_enrolled_applicants, u.total_admissions)),
+			sat_composite: sat_composite(
+				Range::new(u.sat_english_1q, u.sat_english_3q).as_ref(),
+				Range::new(u.sat_math_1q, u.sat_math_3q).as_ref(),
+			),
+			act_composite: Range::new(u.act_composite_1q, u.act_composite_3q),
+			price_gap: u
+				.price_out_of_state
+				.zip(u.price_in_state)
+				.map(|(out_of_state, in_state)| out_of_state - in_state),
+		}
+	}
+}
@@ -0,0 +1,255 @@
+//! filtering and sorting every university, with counts of how many match each facet
+
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, QueryBuilder, Row};
+
+use crate::db::{Pool, DB};
+
+use super::university::Consideration;
+
+/// the most recent year of statistics for each university
+const FROM: &str = "
+	FROM universities AS u
+	LEFT JOIN university_stats AS s ON s.university_id = u.id AND s.year = (
+		SELECT MAX(year) FROM university_stats WHERE university_id = u.id
+	)";
+
+const ACCEPTANCE_RATE: &str =
+	"CAST(100.0 * s.total_admissions / NULLIF(s.total_applicants, 0) AS DOUBLE PRECISION)";
+
+#[derive(Debug, Default)]
+pub struct Filters {
+	/// state abbreviations, any of which match
+	pub states: Vec<String>,
+	pub min_enrollment: Option<i64>,
+	pub max_enrollment: Option<i64>,
+	pub max_price_out_of_state: Option<i64>,
+	pub open_admission: Option<bool>,
+	/// percent
+	pub max_acceptance_rate: Option<f64>,
+	pub considers_test_scores: Option<Consideration>,
+	/// matches universities whose middle 50% of total SAT scores overlaps this range
+	pub sat_range: Option<(i64, i64)>,
+}
+
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Sort {
+	#[default]
+	Name,
+	Enrollment,
+	Price,
+	AcceptanceRate,
+}
+
+impl Sort {
+	fn column(self) -> &'static str {
+		match self {
+			Self::Name => "u.name",
+			Self::Enrollment => "s.total_enrollment",
+			Self::Price => "s.price_out_of_state",
+			Self::AcceptanceRate => "acceptance_rate",
+		}
+	}
+}
+
+#[derive(Debug, Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Order {
+	#[default]
+	Asc,
+	Desc,
+}
+
+/// the facets that have counts, each of which is counted without its own filter
+/// (so choosing one state still shows how many universities are in the others)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Facet {
+	State,
+	OpenAdmission,
+	TestScores,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct BrowseResult {
+	pub id: i64,
+	pub name: String,
+	pub city: String,
+	pub state: String,
+	pub total_enrollment: Option<i64>,
+	pub price_out_of_state: Option<i64>,
+	/// percent of applicants who were admitted
+	pub acceptance_rate: Option<f64>,
+	pub sat_total_1q: Option<i64>,
+	pub sat_total_3q: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FacetCount<T> {
+	pub value: T,
+	pub count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Facets {
+	pub state: Vec<FacetCount<String>>,
+	/// `null` if the university didn't report it
+	pub open_admission: Vec<FacetCount<Option<bool>>>,
+	pub considers_test_scores: Vec<FacetCount<Consideration>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Browse {
+	/// how many universities match, across all pages
+	pub total: i64,
+	pub results: Vec<BrowseResult>,
+	pub facets: Facets,
+}
+
+impl Filters {
+	/// pushes a `WHERE` clause for every filter, except the one for `except`
+	fn push_where<'a>(&'a self, builder: &mut QueryBuilder<'a, DB>, except: Option<Facet>) {
+		builder.push(" WHERE 1 = 1");
+
+		if !self.states.is_empty() && except != Some(Facet::State) {
+			builder.push(" AND u.state IN (");
+			let mut states = builder.separated(", ");
+			for state in &self.states {
+				states.push_bind(state);
+			}
+			builder.push(")");
+		}
+
+		if let Some(min) = self.min_enrollment {
+			builder.push(" AND s.total_enrollment >= ").push_bind(min);
+		}
+
+		if let Some(max) = self.max_enrollment {
+			builder.push(" AND s.total_enrollment <= ").push_bind(max);
+		}
+
+		if let Some(max) = self.max_price_out_of_state {
+			builder.push(" AND s.price_out_of_state <= ").push_bind(max);
+		}
+
+		if let Some(open) = self
+			.open_admission
+			.filter(|_| except != Some(Facet::OpenAdmission))
+		{
+			builder.push(" AND u.open_admission = ").push_bind(open);
+		}
+
+		if let Some(max) = self.max_acceptance_rate {
+			builder
+				.push(format!(" AND {} <= ", ACCEPTANCE_RATE))
+				.push_bind(max);
+		}
+
+		if let Some(considers) = self
+			.considers_test_scores
+			.filter(|_| except != Some(Facet::TestScores))
+		{
+			builder
+				.push(" AND u.considers_test_scores = ")
+				.push_bind(considers);
+		}
+
+		if let Some((low, high)) = self.sat_range {
+			builder
+				.push(" AND s.sat_english_1q + s.sat_math_1q <= ")
+				.push_bind(high)
+				.push(" AND s.sat_english_3q + s.sat_math_3q >= ")
+				.push_bind(low);
+		}
+	}
+
+	async fn count<T>(
+		&self,
+		con: &Pool,
+		facet: Facet,
+		column: &str,
+	) -> sqlx::Result<Vec<FacetCount<T>>>
+	where
+		T: for<'r> sqlx::Decode<'r, DB> + sqlx::Type<DB> + Send + Unpin,
+	{
+		let mut builder =
+			QueryBuilder::new(format!("SELECT {} AS value, COUNT(*) AS count", column));
+		builder.push(FROM);
+		self.push_where(&mut builder, Some(facet));
+		builder.push(format!(" GROUP BY {0} ORDER BY count DESC, {0}", column));
+
+		builder
+			.build()
+			.try_map(|row: <DB as sqlx::Database>::Row| {
+				Ok(FacetCount {
+					value: row.try_get("value")?,
+					count: row.try_get("count")?,
+				})
+			})
+			.fetch_all(con)
+			.await
+	}
+
+	pub async fn browse(
+		&self,
+		con: &Pool,
+		sort: Sort,
+		order: Order,
+		limit: i64,
+		offset: i64,
+	) -> sqlx::Result<Browse> {
+		let mut builder = QueryBuilder::new(format!(
+			"SELECT
+				u.id,
+				u.name,
+				u.city,
+				u.state,
+				s.total_enrollment,
+				s.price_out_of_state,
+				{} AS acceptance_rate,
+				s.sat_english_1q + s.sat_math_1q AS sat_total_1q,
+				s.sat_english_3q + s.sat_math_3q AS sat_total_3q",
+			ACCEPTANCE_RATE
+		));
+		builder.push(FROM);
+		self.push_where(&mut builder, None);
+		builder.push(format!(
+			" ORDER BY {} {} NULLS LAST, u.name LIMIT ",
+			sort.column(),
+			match order {
+				Order::Asc => "ASC",
+				Order::Desc => "DESC",
+			}
+		));
+		builder.push_bind(limit).push(" OFFSET ").push_bind(offset);
+
+		let results = builder
+			.build()
+			.try_map(|row| BrowseResult::from_row(&row))
+			.fetch_all(con)
+			.await?;
+
+		let mut builder = QueryBuilder::new("SELECT COUNT(*)");
+		builder.push(FROM);
+		self.push_where(&mut builder, None);
+		let total = builder
+			.build()
+			.try_map(|row: <DB as sqlx::Database>::Row| row.try_get(0))
+			.fetch_one(con)
+			.await?;
+
+		Ok(Browse {
+			total,
+			results,
+			facets: Facets {
+				state: self.count(con, Facet::State, "u.state").await?,
+				open_admission: self
+					.count(con, Facet::OpenAdmission, "u.open_admission")
+					.await?,
+				considers_test_scores: self
+					.count(con, Facet::TestScores, "u.considers_test_scores")
+					.await?,
+			},
+		})
+	}
+}
@@ -0,0 +1,323 @@
+//! application checklists, generated from what each university requires
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::{Acquire, Executor, Pool};
+
+use super::university::{Consideration, University};
+
+/// an admission requirement that gets its own checklist item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum Requirement {
+	Application,
+	ApplicationFee,
+	Transcript,
+	Recommendations,
+	TestScores,
+	Toefl,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChecklistItem {
+	pub id: i64,
+	pub university_id: i64,
+	/// `null` for items the user added
+	pub requirement: Option<Requirement>,
+	pub label: String,
+	pub done: bool,
+}
+
+/// changes to an item; a generated item's label can't be changed
+#[derive(Debug, Deserialize)]
+pub struct ItemUpdate {
+	pub label: Option<String>,
+	pub done: Option<bool>,
+}
+
+/// the items a university's admission requirements call for, with their labels
+pub fn requirements(university: &University) -> Vec<(Requirement, String)> {
+	let mut items = vec![(
+		Requirement::Application,
+		"Submit the application".to_owned(),
+	)];
+
+	if let Some(fee) = university.application_fee.filter(|&fee| fee > 0) {
+		items.push((
+			Requirement::ApplicationFee,
+			format!("Pay the ${} application fee", fee),
+		));
+	}
+
+	// things that are only considered (or not wanted) aren't worth a reminder
+	let wanted = [
+		(
+			Requirement::Transcript,
+			university.considers_transcript,
+			"Send your transcript",
+		),
+		(
+			Requirement::Recommendations,
+			university.considers_recommendations,
+			"Get letters of recommendation",
+		),
+		(
+			Requirement::TestScores,
+			university.considers_test_scores,
+			"Send SAT or ACT scores",
+		),
+		(
+			Requirement::Toefl,
+			university.considers_toefl,
+			"Send TOEFL scores if you're an international applicant",
+		),
+	];
+
+	for (requirement, consideration, label) in wanted {
+		match consideration {
+			Consideration::Required => items.push((requirement, label.to_owned())),
+			Consideration::Recommended => {
+				items.push((requirement, format!("{} (recommended)", label)))
+			}
+			_ => (),
+		}
+	}
+
+	items
+}
+
+impl ChecklistItem {
+	/// adds any items the university's requirements call for that the user doesn't have yet,
+	/// updates the labels of the ones they do (e.g., if the fee changed),
+	/// and removes generated items for requirements the university dropped.
+	/// run when a university is tracked and whenever universities' requirements change
+	pub async fn generate(
+		con: impl Acquire<'_>,
+		user_id: Uuid,
+		university: &University,
+	) -> sqlx::Result<()> {
+		let mut trans = con.begin().await?;
+		let requirements = requirements(university);
+
+		let existing = sqlx::query!(
+			r#"SELECT requirement AS "requirement!: Requirement" FROM checklist_items
+			WHERE user_id = $1 AND university_id = $2 AND requirement IS NOT NULL"#,
+			user_id,
+			university.id
+		)
+		.fetch_all(&mut trans)
+		.await?;
+
+		for row in existing {
+			if requirements
+				.iter()
+				.all(|(requirement, _)| *requirement != row.requirement)
+			{
+				sqlx::query!(
+					"DELETE FROM checklist_items
+					WHERE user_id = $1 AND university_id = $2 AND requirement = $3",
+					user_id,
+					university.id,
+					row.requirement
+				)
+				.execute(&mut trans)
+				.await?;
+			}
+		}
+
+		for (requirement, label) in requirements {
+			sqlx::query!(
+				"INSERT INTO checklist_items (user_id, university_id, requirement, label)
+				VALUES ($1, $2, $3, $4)
+				ON CONFLICT (user_id, university_id, requirement) DO UPDATE SET label = excluded.label",
+				user_id,
+				university.id,
+				requirement,
+				label
+			)
+			.execute(&mut trans)
+			.await?;
+		}
+
+		trans.commit().await
+	}
+
+	/// regenerates the checklist of every tracked university, for when requirements change
+	pub async fn generate_all(con: &Pool) -> sqlx::Result<()> {
+		let tracked = sqlx::query!(
+			r#"SELECT user_id AS "user_id: Uuid", university_id FROM get_weather
+			ORDER BY university_id"#
+		)
+		.fetch_all(con)
+		.await?;
+
+		let mut university: Option<University> = None;
+		for row in tracked {
+			if university
+				.as_ref()
+				.is_none_or(|u| u.id != row.university_id)
+			{
+				university = University::load(con, row.university_id).await?;
+			}
+
+			if let Some(university) = &university {
+				Self::generate(con, row.user_id, university).await?;
+			}
+		}
+
+		Ok(())
+	}
+
+	/// the user's items for a university, in the order they were added
+	pub async fn load_all(
+		con: impl Executor<'_>,
+		user_id: Uuid,
+		university_id: i64,
+	) -> sqlx::Result<Vec<Self>> {
+		sqlx::query_as!(
+			Self,
+			r#"SELECT
+				id,
+				university_id,
+				requirement AS "requirement: Requirement",
+				label,
+				done
+			FROM checklist_items
+			WHERE user_id = $1 AND university_id = $2
+			ORDER BY id"#,
+			user_id,
+			university_id
+		)
+		.fetch_all(con)
+		.await
+	}
+
+	/// adds an item of the user's own
+	pub async fn create(
+		con: impl Executor<'_>,
+		user_id: Uuid,
+		university_id: i64,
+		label: String,
+	) -> sqlx::Result<Self> {
+		let id = sqlx::query!(
+			"INSERT INTO checklist_items (user_id, university_id, label)
+			VALUES ($1, $2, $3)
+			RETURNING id",
+			user_id,
+			university_id,
+			label
+		)
+		.fetch_one(con)
+		.await?
+		.id;
+
+		Ok(Self {
+			id,
+			university_id,
+			requirement: None,
+			label,
+			done: false,
+		})
+	}
+
+	/// updates an item, returning it if it exists
+	pub async fn update(
+		con: impl Executor<'_> + Clone,
+		user_id: Uuid,
+		university_id: i64,
+		id: i64,
+		update: ItemUpdate,
+	) -> sqlx::Result<Option<Self>> {
+		sqlx::query!(
+			"UPDATE checklist_items SET
+				label = CASE WHEN requirement IS NULL THEN coalesce($1, label) ELSE label END,
+				done = coalesce($2, done)
+			WHERE id = $3 AND user_id = $4 AND university_id = $5",
+			update.label,
+			update.done,
+			id,
+			user_id,
+			university_id
+		)
+		.execute(con.clone())
+		.await?;
+
+		sqlx::query_as!(
+			Self,
+			r#"SELECT
+				id,
+				university_id,
+				requirement AS "requirement: Requirement",
+				label,
+				done
+			FROM checklist_items
+			WHERE id = $1 AND user_id = $2 AND university_id = $3"#,
+			id,
+			user_id,
+			university_id
+		)
+		.fetch_optional(con)
+		.await
+	}
+
+	/// deletes one of the user's own items, returning whether it existed.
+	/// generated items would just come back, so they can only be ticked off
+	pub async fn delete(
+		con: impl Executor<'_>,
+		user_id: Uuid,
+		university_id: i64,
+		id: i64,
+	) -> sqlx::Result<bool> {
+		sqlx::query!(
+			"DELETE FROM checklist_items
+			WHERE id = $1 AND user_id = $2 AND university_id = $3 AND requirement IS NULL",
+			id,
+			user_id,
+			university_id
+		)
+		.execute(con)
+		.await
+		.map(|result| result.rows_affected() > 0)
+	}
+}
+
+#[derive(Debug, Serialize)]
+pub struct UniversityProgress {
+	pub university_id: i64,
+	pub name: String,
+	pub done: usize,
+	pub total: usize,
+	pub items: Vec<ChecklistItem>,
+}
+
+impl UniversityProgress {
+	pub fn new(university: &University, items: Vec<ChecklistItem>) -> Self {
+		Self {
+			university_id: university.id,
+			name: university.name.clone(),
+			done: items.iter().filter(|item| item.done).count(),
+			total: items.len(),
+			items,
+		}
+	}
+}
+
+/// how far along a user is with every application
+#[derive(Debug, Serialize)]
+pub struct Progress {
+	pub done: usize,
+	pub total: usize,
+	pub universities: Vec<UniversityProgress>,
+}
+
+impl From<Vec<UniversityProgress>> for Progress {
+	fn from(universities: Vec<UniversityProgress>) -> Self {
+		Self {
+			done: universities.iter().map(|progress| progress.done).sum(),
+			total: universities.iter().map(|progress| progress.total).sum(),
+			universities,
+		}
+	}
+}
@@ -0,0 +1,69 @@
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, NaiveDate};
+use chrono_tz::Tz;
+use serde::Serialize;
+
+use super::weather::{is_precipitation, Weather};
+
+/// typical weather in a month, from the observations it's given (usually the last year)
+#[derive(Debug, Serialize)]
+pub struct MonthClimate {
+	/// 1 = January
+	pub month: u32,
+	/// number of observations. when there are none, everything else is `null`
+	pub samples: usize,
+	pub mean_temperature: Option<f64>,
+	/// average of each day's highest observed temperature
+	pub mean_high: Option<f64>,
+	/// average of each day's lowest observed temperature
+	pub mean_low: Option<f64>,
+	pub mean_humidity: Option<f64>,
+	/// fraction of observations with precipitation
+	pub precipitation_frequency: Option<f64>,
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> Option<f64> {
+	let (sum, count) = values.fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+	(count > 0).then(|| sum / count as f64)
+}
+
+/// summarizes the weather by month (in `tz`), always returning all 12 months
+pub fn climatology(weather: &[Weather], tz: &Tz) -> Vec<MonthClimate> {
+	let mut months: [Vec<&Weather>; 12] = Default::default();
+	// (high, low) for each local day
+	let mut days = BTreeMap::<NaiveDate, (f64, f64)>::new();
+
+	for observation in weather {
+		let date = observation.time.with_timezone(tz).date().naive_local();
+		months[date.month0() as usize].push(observation);
+
+		let (high, low) = days
+			.entry(date)
+			.or_insert((observation.temperature, observation.temperature));
+		*high = high.max(observation.temperature);
+		*low = low.min(observation.temperature);
+	}
+
+	months
+		.iter()
+		.zip(1..)
+		.map(|(observations, month)| {
+			let days = || days.iter().filter(|(date, _)| date.month() == month);
+
+			MonthClimate {
+				month,
+				samples: observations.len(),
+				mean_temperature: mean(observations.iter().map(|w| w.temperature)),
+				mean_high: mean(days().map(|(_, (high, _))| *high)),
+				mean_low: mean(days().map(|(_, (_, low))| *low)),
+				mean_humidity: mean(observations.iter().map(|w| w.humidity)),
+				precipitation_frequency: mean(
+					observations
+						.iter()
+						.map(|w| is_precipitation(w.weather_type) as u8 as f64),
+				),
+			}
+		})
+		.collect()
+}
@@ -0,0 +1,71 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use super::{
+	admissions::AdmissionsSummary, climatology::MonthClimate, daily::DailyForecast,
+	university::University, weather::Weather,
+};
+
+/// one university's column in a comparison table
+#[derive(Debug, Serialize)]
+pub struct Compared {
+	pub university: University,
+	pub admissions: AdmissionsSummary,
+	/// the current weather, if it could be loaded
+	pub weather: Option<Weather>,
+	/// one entry for each of `Comparison::dates`, `null` if there's no forecast for it
+	pub daily: Vec<Option<DailyForecast>>,
+	/// one entry for each month, January first
+	pub climatology: Vec<MonthClimate>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Comparison {
+	/// every (local) date that any university has a forecast for, in order
+	pub dates: Vec<NaiveDate>,
+	pub universities: Vec<Compared>,
+}
+
+/// everything loaded for a university, before it's aligned with the others
+pub struct Loaded {
+	pub university: University,
+	pub weather: Option<Weather>,
+	pub daily: Vec<DailyForecast>,
+	pub climatology: Vec<MonthClimate>,
+}
+
+impl Comparison {
+	/// lines up the daily forecasts of every university by date
+	pub fn new(loaded: Vec<Loaded>) -> Self {
+		let mut dates: Vec<_> = loaded
+			.iter()
+			.flat_map(|l| l.daily.iter().map(|day| day.date))
+			.collect();
+		dates.sort_unstable();
+		dates.dedup();
+
+		let universities = loaded
+			.into_iter()
+			.map(|l| {
+				let mut daily = l.daily.into_iter().peekable();
+				let aligned = dates
+					.iter()
+					.map(|date| daily.next_if(|day| day.date == *date))
+					.collect();
+
+				Compared {
+					admissions: AdmissionsSummary::new(&l.university),
+					university: l.university,
+					weather: l.weather,
+					daily: aligned,
+					climatology: l.climatology,
+				}
+			})
+			.collect();
+
+		Self {
+			dates,
+			universities,
+		}
+	}
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use serde::Serialize;
+
+use super::forecast::Forecast;
+
+/// summary of one local day of forecasts
+#[derive(Debug, Serialize)]
+pub struct DailyForecast {
+	pub university_id: i64,
+	/// the day, in the university's timezone
+	pub date: NaiveDate,
+	pub high: f64,
+	pub low: f64,
+	/// highest chance of precipitation during the day
+	pub precipitation_chance: f64,
+	/// the condition that best represents the daytime weather
+	pub weather_id: i64,
+	pub weather_description: String,
+	pub day_icon: String,
+	pub night_icon: String,
+}
+
+/// orders conditions by how much they matter to someone deciding what to wear:
+/// thunderstorm > snow > rain > drizzle > atmosphere (fog, etc.) > clouds > clear
+fn severity(weather_id: i64) -> (u8, i64) {
+	let group = match weather_id / 100 {
+		2 => 6,
+		6 => 5,
+		5 => 4,
+		3 => 3,
+		7 => 2,
+		_ if weather_id == 800 => 0,
+		_ => 1,
+	};
+
+	(group, weather_id)
+}
+
+/// the most common condition, with ties going to the more severe one
+fn representative<'a>(forecasts: impl Iterator<Item = &'a Forecast>) -> Option<&'a Forecast> {
+	let mut counts = HashMap::<i64, (u32, &Forecast)>::new();
+
+	for forecast in forecasts {
+		counts.entry(forecast.weather_id).or_insert((0, forecast)).0 += 1;
+	}
+
+	counts
+		.into_values()
+		.max_by_key(|(count, forecast)| (*count, severity(forecast.weather_id)))
+		.map(|(_, forecast)| forecast)
+}
+
+fn icon(weather_id: i64, is_day: bool) -> String {
+	format!(
+		"/static/assets/{}-{}.svg",
+		weather_id,
+		if is_day { "day" } else { "night" }
+	)
+}
+
+fn summarize(day: &[Forecast], date: NaiveDate) -> DailyForecast {
+	let all = || day.iter();
+	let daytime = representative(all().filter(|f| f.is_day));
+	let nighttime = representative(all().filter(|f| !f.is_day));
+	// `day` is never empty, so there is always a representative
+	let main = daytime.or(nighttime).unwrap();
+
+	DailyForecast {
+		university_id: main.university_id,
+		date,
+		high: all().map(|f| f.temperature).fold(f64::MIN, f64::max),
+		low: all().map(|f| f.temperature).fold(f64::MAX, f64::min),
+		precipitation_chance: all().map(|f| f.precipitation_chance).fold(0.0, f64::max),
+		weather_id: main.weather_id,
+		weather_description: main.weather_description.clone(),
+		day_icon: icon(main.weather_id, true),
+		night_icon: icon(nighttime.unwrap_or(main).weather_id, false),
+	}
+}
+
+/// groups forecasts (sorted by time) by day in `tz` and summarizes each day
+pub fn summarize_days(forecasts: &[Forecast], tz: &Tz) -> Vec<DailyForecast> {
+	let date_of = |forecast: &Forecast| forecast.time.with_timezone(tz).date().naive_local();
+
+	forecasts
+		.chunk_by(|a, b| date_of(a) == date_of(b))
+		.map(|day| summarize(day, date_of(&day[0])))
+		.collect()
+}
@@ -0,0 +1,267 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::Executor;
+
+use super::{local_time::to_utc, timezone::Timezone};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum DeadlineKind {
+	EarlyAction,
+	EarlyDecision,
+	Regular,
+	FinancialAid,
+	Custom,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Deadline {
+	pub id: i64,
+	pub university_id: i64,
+	pub kind: DeadlineKind,
+	pub name: Option<String>,
+	/// due by the end of this day, in the university's timezone
+	pub due: NaiveDate,
+	pub notes: Option<String>,
+}
+
+/// a deadline as sent by a client, to create or replace one
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeadlineData {
+	pub kind: DeadlineKind,
+	pub name: Option<String>,
+	pub due: NaiveDate,
+	pub notes: Option<String>,
+}
+
+impl From<Deadline> for DeadlineData {
+	fn from(deadline: Deadline) -> Self {
+		Self {
+			kind: deadline.kind,
+			name: deadline.name,
+			due: deadline.due,
+			notes: deadline.notes,
+		}
+	}
+}
+
+impl DeadlineData {
+	pub fn validate(&self) -> Result<(), &'static str> {
+		let has_name = self
+			.name
+			.as_deref()
+			.is_some_and(|name| !name.trim().is_empty());
+
+		if self.kind == DeadlineKind::Custom && !has_name {
+			Err("custom deadlines need a name")
+		} else {
+			Ok(())
+		}
+	}
+}
+
+/// the moment a deadline is due: the end of its day in `tz`
+pub fn due_at(due: NaiveDate, tz: &Tz) -> DateTime<Utc> {
+	to_utc(tz, due.and_hms(23, 59, 59))
+}
+
+impl Deadline {
+	/// the user's deadlines for a university, soonest first
+	pub async fn load_all(
+		con: impl Executor<'_>,
+		user_id: Uuid,
+		university_id: i64,
+	) -> sqlx::Result<Vec<Self>> {
+		sqlx::query_as!(
+			Self,
+			r#"SELECT
+				id,
+				university_id,
+				kind AS "kind: DeadlineKind",
+				name,
+				due AS "due: NaiveDate",
+				notes
+			FROM deadlines
+			WHERE user_id = $1 AND university_id = $2
+			ORDER BY due"#,
+			user_id,
+			university_id
+		)
+		.fetch_all(con)
+		.await
+	}
+
+	pub async fn create(
+		con: impl Executor<'_>,
+		user_id: Uuid,
+		university_id: i64,
+		data: DeadlineData,
+	) -> sqlx::Result<Self> {
+		let id = sqlx::query!(
+			"INSERT INTO deadlines (user_id, university_id, kind, name, due, notes)
+			VALUES ($1, $2, $3, $4, $5, $6)
+			RETURNING id",
+			user_id,
+			university_id,
+			data.kind,
+			data.name,
+			data.due,
+			data.notes
+		)
+		.fetch_one(con)
+		.await?
+		.id;
+
+		Ok(Self {
+			id,
+			university_id,
+			kind: data.kind,
+			name: data.name,
+			due: data.due,
+			notes: data.notes,
+		})
+	}
+
+	/// replaces a deadline, returning it if it exists.
+	/// changing when it's due starts the reminders over
+	pub async fn update(
+		con: impl Executor<'_>,
+		user_id: Uuid,
+		university_id: i64,
+		id: i64,
+		data: DeadlineData,
+	) -> sqlx::Result<Option<Self>> {
+		let result = sqlx::query!(
+			"UPDATE deadlines SET
+				kind = $1,
+				name = $2,
+				notes = $3,
+				reminders_sent = CASE WHEN due = $4 THEN reminders_sent ELSE 0 END,
+				due = $4
+			WHERE id = $5 AND user_id = $6 AND university_id = $7",
+			data.kind,
+			data.name,
+			data.notes,
+			data.due,
+			id,
+			user_id,
+			university_id
+		)
+		.execute(con)
+		.await?;
+
+		Ok((result.rows_affected() > 0).then_some(Self {
+			id,
+			university_id,
+			kind: data.kind,
+			name: data.name,
+			due: data.due,
+			notes: data.notes,
+		}))
+	}
+
+	/// deletes a deadline, returning whether it existed
+	pub async fn delete(
+		con: impl Executor<'_>,
+		user_id: Uuid,
+		university_id: i64,
+		id: i64,
+	) -> sqlx::Result<bool> {
+		sqlx::query!(
+			"DELETE FROM deadlines WHERE id = $1 AND user_id = $2 AND university_id = $3",
+			id,
+			user_id,
+			university_id
+		)
+		.execute(con)
+		.await
+		.map(|result| result.rows_affected() > 0)
+	}
+}
+
+/// a deadline that might need a reminder, with what's needed to write one
+#[derive(Debug)]
+pub struct PendingReminder {
+	pub deadline: Deadline,
+	pub user_id: Uuid,
+	pub username: String,
+	pub university_name: String,
+	pub timezone: Timezone,
+	pub reminders_sent: i64,
+}
+
+impl PendingReminder {
+	/// deadlines due between `from` and `to` that haven't had every reminder yet.
+	/// deadlines are kept when a university is untracked (in case it's tracked again),
+	/// but only tracked universities get reminders
+	pub async fn load(
+		con: impl Executor<'_>,
+		from: NaiveDate,
+		to: NaiveDate,
+		max_reminders: i64,
+	) -> sqlx::Result<Vec<Self>> {
+		sqlx::query!(
+			r#"SELECT
+				d.id,
+				d.university_id,
+				d.kind AS "kind: DeadlineKind",
+				d.name,
+				d.due AS "due: NaiveDate",
+				d.notes,
+				d.reminders_sent,
+				d.user_id AS "user_id: Uuid",
+				users.username,
+				universities.name AS university_name,
+				universities.timezone AS "timezone: Timezone"
+			FROM deadlines AS d
+			INNER JOIN users ON users.id = d.user_id
+			INNER JOIN universities ON universities.id = d.university_id
+			INNER JOIN get_weather AS tracked
+				ON (tracked.user_id, tracked.university_id) = (d.user_id, d.university_id)
+			WHERE d.due BETWEEN $1 AND $2 AND d.reminders_sent < $3"#,
+			from,
+			to,
+			max_reminders
+		)
+		.fetch_all(con)
+		.await
+		.map(|rows| {
+			rows.into_iter()
+				.map(|row| Self {
+					deadline: Deadline {
+						id: row.id,
+						university_id: row.university_id,
+						kind: row.kind,
+						name: row.name,
+						due: row.due,
+						notes: row.notes,
+					},
+					user_id: row.user_id,
+					username: row.username,
+					university_name: row.university_name,
+					timezone: row.timezone,
+					reminders_sent: row.reminders_sent,
+				})
+				.collect()
+		})
+	}
+
+	pub async fn mark_sent(
+		con: impl Executor<'_>,
+		deadline_id: i64,
+		reminders_sent: i64,
+	) -> sqlx::Result<()> {
+		sqlx::query!(
+			"UPDATE deadlines SET reminders_sent = $1 WHERE id = $2",
+			reminders_sent,
+			deadline_id
+		)
+		.execute(con)
+		.await
+		.map(|_| ())
+	}
+}
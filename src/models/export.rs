@@ -0,0 +1,284 @@
+//! everything a user is tracking, in a form that can be imported into another account
+//! or another clime, where the university ids might not be the same
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::Executor;
+
+use super::{
+	deadline::DeadlineData,
+	profile::AdmissionsProfile,
+	timezone::Timezone,
+	tracked::{Category, TrackingData},
+	user::Units,
+};
+
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Preferences {
+	pub units: Units,
+	pub timezone: Option<Timezone>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExportedUniversity {
+	/// just for reference, since universities are matched by `unitid`, `website` or `name`
+	pub id: Option<i64>,
+	/// IPEDS unit id
+	pub unitid: Option<i64>,
+	pub name: Option<String>,
+	pub website: Option<String>,
+	pub notes: Option<String>,
+	pub rating: Option<i64>,
+	pub category: Option<Category>,
+	#[serde(default)]
+	pub tags: Vec<String>,
+	#[serde(default)]
+	pub deadlines: Vec<DeadlineData>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Export {
+	pub preferences: Option<Preferences>,
+	pub profile: Option<AdmissionsProfile>,
+	pub universities: Vec<ExportedUniversity>,
+}
+
+/// a row of a spreadsheet, with one university per row.
+/// the preferences are repeated on every row, and the first row with `units` is used
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CsvRow {
+	pub id: Option<i64>,
+	pub unitid: Option<i64>,
+	pub name: Option<String>,
+	pub website: Option<String>,
+	pub notes: Option<String>,
+	pub rating: Option<i64>,
+	pub category: Option<Category>,
+	/// a JSON array of strings, since tags can have any punctuation in them
+	#[serde(default)]
+	pub tags: String,
+	/// a JSON array of deadlines, like in the JSON export
+	#[serde(default)]
+	pub deadlines: String,
+	pub units: Option<Units>,
+	pub timezone: Option<Timezone>,
+}
+
+impl CsvRow {
+	pub fn new(
+		university: ExportedUniversity,
+		preferences: Option<&Preferences>,
+	) -> serde_json::Result<Self> {
+		Ok(Self {
+			id: university.id,
+			unitid: university.unitid,
+			name: university.name,
+			website: university.website,
+			notes: university.notes,
+			rating: university.rating,
+			category: university.category,
+			tags: serde_json::to_string(&university.tags)?,
+			deadlines: serde_json::to_string(&university.deadlines)?,
+			units: preferences.map(|preferences| preferences.units),
+			timezone: preferences.and_then(|preferences| preferences.timezone),
+		})
+	}
+
+	/// `None` if the row doesn't have `units`
+	pub fn preferences(&self) -> Option<Preferences> {
+		self.units.map(|units| Preferences {
+			units,
+			timezone: self.timezone,
+		})
+	}
+}
+
+/// an empty cell is an empty list, so spreadsheets made by hand can leave it out
+fn parse_list<T: serde::de::DeserializeOwned>(cell: &str) -> serde_json::Result<Vec<T>> {
+	if cell.trim().is_empty() {
+		Ok(Vec::new())
+	} else {
+		serde_json::from_str(cell)
+	}
+}
+
+impl TryFrom<CsvRow> for ExportedUniversity {
+	type Error = String;
+
+	fn try_from(row: CsvRow) -> Result<Self, Self::Error> {
+		Ok(Self {
+			id: row.id,
+			unitid: row.unitid,
+			name: row.name,
+			website: row.website,
+			notes: row.notes,
+			rating: row.rating,
+			category: row.category,
+			tags: parse_list(&row.tags).map_err(|err| format!("invalid tags: {}", err))?,
+			deadlines: parse_list(&row.deadlines)
+				.map_err(|err| format!("invalid deadlines: {}", err))?,
+		})
+	}
+}
+
+impl ExportedUniversity {
+	/// what the user wrote about the university, if anything
+	pub fn tracking(&self) -> Option<TrackingData> {
+		if self.notes.is_none()
+			&& self.rating.is_none()
+			&& self.category.is_none()
+			&& self.tags.is_empty()
+		{
+			None
+		} else {
+			Some(TrackingData {
+				notes: self.notes.clone(),
+				rating: self.rating,
+				category: self.category,
+				tags: self.tags.clone(),
+			})
+		}
+	}
+
+	/// how to refer to the university in errors
+	pub fn describe(&self) -> String {
+		self.name
+			.clone()
+			.or_else(|| self.unitid.map(|unitid| format!("UNITID {}", unitid)))
+			.or_else(|| self.website.clone())
+			.or_else(|| self.id.map(|id| format!("id {}", id)))
+			.unwrap_or_else(|| "a university with no name".to_owned())
+	}
+
+	/// finds the university by its IPEDS unit id, then its website, then its name
+	pub async fn find(&self, con: impl Executor<'_> + Clone) -> sqlx::Result<Option<i64>> {
+		if let Some(unitid) = self.unitid {
+			let id = sqlx::query!("SELECT id FROM universities WHERE unitid = $1", unitid)
+				.fetch_optional(con.clone())
+				.await?;
+
+			if let Some(row) = id {
+				return Ok(Some(row.id));
+			}
+		}
+
+		if let Some(website) = self.website.as_deref().map(normalize_website) {
+			// IPEDS websites are written many ways (`www.mit.edu/`, `https://mit.edu`)
+			let id = sqlx::query!(
+				"SELECT id FROM universities
+				WHERE rtrim(replace(replace(replace(lower(website), 'https://', ''), 'http://', ''), 'www.', ''), '/') = $1
+				LIMIT 1",
+				website
+			)
+			.fetch_optional(con.clone())
+			.await?;
+
+			if let Some(row) = id {
+				return Ok(Some(row.id));
+			}
+		}
+
+		if let Some(name) = &self.name {
+			let id = sqlx::query!(
+				"SELECT id FROM universities WHERE lower(name) = lower($1) LIMIT 1",
+				name
+			)
+			.fetch_optional(con)
+			.await?;
+
+			if let Some(row) = id {
+				return Ok(Some(row.id));
+			}
+		}
+
+		Ok(None)
+	}
+}
+
+fn normalize_website(website: &str) -> String {
+	let website = website.trim().to_lowercase();
+	let website = website
+		.trim_start_matches("https://")
+		.trim_start_matches("http://")
+		.trim_start_matches("www.");
+
+	website.trim_end_matches('/').to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+	use chrono::NaiveDate;
+
+	use super::*;
+	use crate::models::deadline::DeadlineKind;
+
+	#[test]
+	fn csv_round_trip() {
+		let university = ExportedUniversity {
+			id: Some(1),
+			unitid: Some(166683),
+			name: Some("Massachusetts Institute of Technology".to_owned()),
+			website: Some("web.mit.edu/".to_owned()),
+			notes: Some("visit in \"spring\", maybe; or fall".to_owned()),
+			rating: Some(5),
+			category: Some(Category::Reach),
+			tags: vec!["boston; ma".to_owned(), "stem".to_owned()],
+			deadlines: vec![DeadlineData {
+				kind: DeadlineKind::Custom,
+				name: Some("Interview; round 2".to_owned()),
+				due: NaiveDate::from_ymd(2023, 1, 1),
+				notes: Some("bring a portfolio".to_owned()),
+			}],
+		};
+		let preferences = Preferences {
+			units: Units::Metric,
+			timezone: Some(Timezone(chrono_tz::America::New_York)),
+		};
+
+		let mut writer = csv::Writer::from_writer(Vec::new());
+		writer
+			.serialize(CsvRow::new(university, Some(&preferences)).unwrap())
+			.unwrap();
+		let csv = writer.into_inner().unwrap();
+
+		let row: CsvRow = csv::Reader::from_reader(csv.as_slice())
+			.deserialize()
+			.next()
+			.unwrap()
+			.unwrap();
+		assert_eq!(row.preferences(), Some(preferences));
+
+		let university = ExportedUniversity::try_from(row).unwrap();
+		assert_eq!(university.unitid, Some(166683));
+		assert_eq!(
+			university.notes.as_deref(),
+			Some("visit in \"spring\", maybe; or fall")
+		);
+		assert_eq!(university.rating, Some(5));
+		assert_eq!(university.category, Some(Category::Reach));
+		assert_eq!(university.tags, ["boston; ma", "stem"]);
+
+		let deadline = &university.deadlines[0];
+		assert_eq!(deadline.kind, DeadlineKind::Custom);
+		assert_eq!(deadline.name.as_deref(), Some("Interview; round 2"));
+		assert_eq!(deadline.due, NaiveDate::from_ymd(2023, 1, 1));
+		assert_eq!(deadline.notes.as_deref(), Some("bring a portfolio"));
+	}
+
+	#[test]
+	fn empty_lists() {
+		let csv = "id,unitid,name,website,notes,rating,category,tags,deadlines,units,timezone\n\
+			,,Stanford University,,,,,,,,\n";
+
+		let row: CsvRow = csv::Reader::from_reader(csv.as_bytes())
+			.deserialize()
+			.next()
+			.unwrap()
+			.unwrap();
+		assert_eq!(row.preferences(), None);
+
+		let university = ExportedUniversity::try_from(row).unwrap();
+		assert!(university.tags.is_empty());
+		assert!(university.deadlines.is_empty());
+	}
+}
@@ -0,0 +1,197 @@
+//! how likely a user is to be admitted to a university, compared to who it usually admits
+
+use serde::Serialize;
+
+use super::{
+	admissions::{percent, sat_composite},
+	profile::AdmissionsProfile,
+	stats::Range,
+	university::{Consideration, University},
+};
+
+/// universities that admit fewer than this percent of applicants are a reach for everyone
+const REACH_ACCEPTANCE_RATE: f64 = 15.0;
+
+/// universities that admit fewer than this percent of applicants are never a safety
+const SAFETY_ACCEPTANCE_RATE: f64 = 30.0;
+
+/// GPA at or above this is strong, and below `LOW_GPA` is weak
+const HIGH_GPA: f64 = 3.7;
+const LOW_GPA: f64 = 3.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Fit {
+	Reach,
+	Match,
+	Safety,
+}
+
+/// where a score is compared to the middle 50% of admitted students
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Position {
+	Below,
+	Within,
+	Above,
+}
+
+impl Position {
+	fn of(score: i64, range: &Range) -> Self {
+		if score < range.low {
+			Self::Below
+		} else if score > range.high {
+			Self::Above
+		} else {
+			Self::Within
+		}
+	}
+
+	fn value(self) -> f64 {
+		match self {
+			Self::Below => -1.0,
+			Self::Within => 0.0,
+			Self::Above => 1.0,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Factor {
+	Sat,
+	Act,
+	ActEnglish,
+	ActMath,
+	Gpa,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Compared {
+	pub factor: Factor,
+	pub position: Position,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Classification {
+	/// `null` if there isn't enough information (no profile, or nothing to compare against)
+	pub fit: Option<Fit>,
+	/// the parts of the profile that were compared
+	pub compared: Vec<Compared>,
+}
+
+/// whether the university uses an admissions factor at all
+fn uses(consideration: Consideration) -> bool {
+	matches!(
+		consideration,
+		Consideration::Required | Consideration::Recommended | Consideration::Considered
+	)
+}
+
+impl Classification {
+	/// compares the profile's test scores and GPA against the university's middle 50%,
+	/// each only if the university requires, recommends or considers it.
+	/// a GPA has no middle 50% in IPEDS, so it's compared to `LOW_GPA` and `HIGH_GPA` instead.
+	/// on average, below the range is a reach, above is a safety and otherwise a match.
+	/// very selective universities are always a reach, and open admission ones a safety.
+	pub fn new(profile: Option<&AdmissionsProfile>, university: &University) -> Self {
+		let u = university;
+		let mut compared = Vec::new();
+
+		if let Some(p) = profile {
+			if uses(u.considers_test_scores) {
+				let sat = sat_composite(
+					Range::new(u.sat_english_1q, u.sat_english_3q).as_ref(),
+					Range::new(u.sat_math_1q, u.sat_math_3q).as_ref(),
+				);
+
+				if let (Some(range), Some(english), Some(math)) = (sat, p.sat_english, p.sat_math) {
+					compared.push(Compared {
+						factor: Factor::Sat,
+						position: Position::of(english + math, &range),
+					});
+				}
+
+				let act = [
+					(
+						Factor::Act,
+						p.act_composite,
+						u.act_composite_1q,
+						u.act_composite_3q,
+					),
+					(
+						Factor::ActEnglish,
+						p.act_english,
+						u.act_english_1q,
+						u.act_english_3q,
+					),
+					(Factor::ActMath, p.act_math, u.act_math_1q, u.act_math_3q),
+				];
+
+				for (factor, score, low, high) in act {
+					if let (Some(range), Some(score)) = (Range::new(low, high), score) {
+						compared.push(Compared {
+							factor,
+							position: Position::of(score, &range),
+						});
+					}
+				}
+			}
+
+			if uses(u.considers_gpa) {
+				if let Some(gpa) = p.gpa {
+					let position = if gpa >= HIGH_GPA {
+						Position::Above
+					} else if gpa < LOW_GPA {
+						Position::Below
+					} else {
+						Position::Within
+					};
+
+					compared.push(Compared {
+						factor: Factor::Gpa,
+						position,
+					});
+				}
+			}
+		}
+
+		if u.open_admission == Some(true) {
+			return Self {
+				fit: Some(Fit::Safety),
+				compared,
+			};
+		}
+
+		if compared.is_empty() {
+			return Self {
+				fit: None,
+				compared,
+			};
+		}
+
+		let average =
+			compared.iter().map(|c| c.position.value()).sum::<f64>() / compared.len() as f64;
+
+		let mut fit = if average <= -0.5 {
+			Fit::Reach
+		} else if average >= 0.5 {
+			Fit::Safety
+		} else {
+			Fit::Match
+		};
+
+		if let Some(rate) = percent(u.total_admissions, u.total_applicants) {
+			if rate < REACH_ACCEPTANCE_RATE {
+				fit = Fit::Reach;
+			} else if rate < SAFETY_ACCEPTANCE_RATE && fit == Fit::Safety {
+				fit = Fit::Match;
+			}
+		}
+
+		Self {
+			fit: Some(fit),
+			compared,
+		}
+	}
+}
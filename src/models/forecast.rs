@@ -1,17 +1,18 @@
-use chrono::{naive::serde::ts_milliseconds, NaiveDateTime, Utc};
-use chrono_tz::Tz;
+use chrono::{serde::ts_milliseconds, DateTime, Utc};
 use openweather_api::{forecast::PartOfDay, Client, Coordinates};
 use serde::Serialize;
 
 use crate::db::Executor;
 
+use super::{local_time::to_utc, timezone::Timezone};
+
 #[derive(Debug, Serialize)]
 pub struct Forecast {
 	pub university_id: i64,
 	#[serde(with = "ts_milliseconds")]
-	pub time: NaiveDateTime,
+	pub time: DateTime<Utc>,
 	#[serde(with = "ts_milliseconds")]
-	pub fetched_at: NaiveDateTime,
+	pub fetched_at: DateTime<Utc>,
 	pub temperature: f64,
 	pub feels_like: f64,
 	pub weather_id: i64,
@@ -31,7 +32,7 @@ impl Forecast {
 		coords: &Coordinates,
 		limit: u16,
 	) -> openweather_api::Result<Vec<Self>> {
-		let fetched_at = Utc::now().naive_utc();
+		let fetched_at = Utc::now();
 
 		client.forecast(limit, coords).await.map(|resp| {
 			resp.list
@@ -41,7 +42,7 @@ impl Forecast {
 					let main_weather = f.weather.into_iter().next().unwrap();
 					Self {
 						university_id,
-						time: f.time.naive_utc(),
+						time: f.time,
 						fetched_at,
 						temperature: f.main.temp.0,
 						weather_id: main_weather.id as u32 as i64,
@@ -59,6 +60,8 @@ impl Forecast {
 	}
 
 	pub async fn put(&self, con: impl Executor<'_>) -> sqlx::Result<()> {
+		// timestamps are stored as naive UTC
+		let (time, fetched_at) = (self.time.naive_utc(), self.fetched_at.naive_utc());
 		sqlx::query!(
 			"INSERT INTO forecasts (
 				university_id,
@@ -74,33 +77,11 @@ impl Forecast {
 				precipitation_chance,
 				is_day
 			) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
-				ON CONFLICT(university_id, time) DO UPDATE SET (
-					fetched_at,
-					temperature,
-					feels_like,
-					weather_id,
-					weather_description,
-					humidity,
-					pressure,
-					wind_speed,
-					precipitation_chance,
-					is_day
-				) = (
-					excluded.fetched_at,
-					excluded.temperature,
-					excluded.feels_like,
-					excluded.weather_id,
-					excluded.weather_description,
-					excluded.humidity,
-					excluded.pressure,
-					excluded.wind_speed,
-					excluded.precipitation_chance,
-					excluded.is_day
-				)
+				ON CONFLICT(university_id, time, fetched_at) DO NOTHING
 			",
 			self.university_id,
-			self.time,
-			self.fetched_at,
+			time,
+			fetched_at,
 			self.temperature,
 			self.feels_like,
 			self.weather_id,
@@ -116,6 +97,7 @@ impl Forecast {
 		.map(|_| ())
 	}
 
+	/// gets the latest revision of the `limit` most recent forecasts
 	pub async fn get_most_recent(
 		con: impl Executor<'_>,
 		university_id: i64,
@@ -123,10 +105,27 @@ impl Forecast {
 	) -> sqlx::Result<Vec<Self>> {
 		sqlx::query_as!(
 			Self,
-			"SELECT * FROM forecasts
+			r#"SELECT
+				university_id,
+				time AS "time: DateTime<Utc>",
+				fetched_at AS "fetched_at: DateTime<Utc>",
+				temperature,
+				feels_like,
+				weather_id,
+				weather_description,
+				humidity,
+				pressure,
+				wind_speed,
+				precipitation_chance,
+				is_day
+			FROM forecasts AS f
 			WHERE university_id = $1
+				AND fetched_at = (
+					SELECT MAX(fetched_at) FROM forecasts
+					WHERE university_id = f.university_id AND time = f.time
+				)
 			ORDER BY time DESC
-			LIMIT $2",
+			LIMIT $2"#,
 			university_id,
 			limit
 		)
@@ -134,12 +133,85 @@ impl Forecast {
 		.await
 	}
 
+	/// gets the latest revision of the forecasts for times in `[start, end]`
+	pub async fn get_latest_between(
+		con: impl Executor<'_>,
+		university_id: i64,
+		start: DateTime<Utc>,
+		end: DateTime<Utc>,
+	) -> sqlx::Result<Vec<Self>> {
+		let (start, end) = (start.naive_utc(), end.naive_utc());
+		sqlx::query_as!(
+			Self,
+			r#"SELECT
+				university_id,
+				time AS "time: DateTime<Utc>",
+				fetched_at AS "fetched_at: DateTime<Utc>",
+				temperature,
+				feels_like,
+				weather_id,
+				weather_description,
+				humidity,
+				pressure,
+				wind_speed,
+				precipitation_chance,
+				is_day
+			FROM forecasts AS f
+			WHERE university_id = $1
+				AND time BETWEEN $2 AND $3
+				AND fetched_at = (
+					SELECT MAX(fetched_at) FROM forecasts
+					WHERE university_id = f.university_id AND time = f.time
+				)
+			ORDER BY time"#,
+			university_id,
+			start,
+			end
+		)
+		.fetch_all(con)
+		.await
+	}
+
+	/// gets every revision of the forecasts for times in `[start, end]`
+	pub async fn get_revisions_between(
+		con: impl Executor<'_>,
+		university_id: i64,
+		start: DateTime<Utc>,
+		end: DateTime<Utc>,
+	) -> sqlx::Result<Vec<Self>> {
+		let (start, end) = (start.naive_utc(), end.naive_utc());
+		sqlx::query_as!(
+			Self,
+			r#"SELECT
+				university_id,
+				time AS "time: DateTime<Utc>",
+				fetched_at AS "fetched_at: DateTime<Utc>",
+				temperature,
+				feels_like,
+				weather_id,
+				weather_description,
+				humidity,
+				pressure,
+				wind_speed,
+				precipitation_chance,
+				is_day
+			FROM forecasts
+			WHERE university_id = $1 AND time BETWEEN $2 AND $3
+			ORDER BY time, fetched_at"#,
+			university_id,
+			start,
+			end
+		)
+		.fetch_all(con)
+		.await
+	}
+
 	pub async fn get_all_since(
 		con: impl Executor<'_> + Clone,
 		university_id: i64,
 	) -> sqlx::Result<Option<Vec<Self>>> {
 		let tz = match sqlx::query!(
-			"SELECT timezone FROM universities WHERE id = $1",
+			r#"SELECT timezone AS "timezone: Timezone" FROM universities WHERE id = $1"#,
 			university_id
 		)
 		.fetch_optional(con.clone())
@@ -149,17 +221,9 @@ impl Forecast {
 			None => return Ok(None),
 		};
 
-		// TODO this shouldnt happen-- should we add error to ret type?
-		let tz = tz
-			.parse::<Tz>()
-			.map_err(|_| format!("Could not load {} as timezone", tz))
-			.unwrap();
-
-		let not_before = Utc::now()
-			.with_timezone(&tz)
-			.date()
-			.and_hms(0, 0, 0)
-			.naive_utc();
+		// midnight doesn't exist on days where DST starts at midnight
+		let today = Utc::now().with_timezone(&*tz).date().naive_local();
+		let not_before = to_utc(&tz, today.and_hms(0, 0, 0));
 
 		// worst-case scenario: 11:59 pm => 40 max + 8/day = 48
 		const LIMIT: u32 = 48;
@@ -0,0 +1,164 @@
+//! how far each university is from a user's home, and how different it is
+
+use chrono::{Offset, Utc};
+use chrono_tz::Tz;
+use openweather_api::Coordinates;
+use serde::{Deserialize, Serialize};
+
+use crate::geo::distance::haversine_km;
+
+use super::{university::University, weather::Weather};
+
+/// roads are rarely straight, so driving is about this much further than the great-circle distance
+const ROAD_FACTOR: f64 = 1.3;
+/// average driving speed, including highways and towns
+const DRIVING_KM_PER_HOUR: f64 = 90.0;
+
+/// a user's home, as coordinates or a US zip code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Home {
+	/// looked up from the zip code if it's set
+	pub latitude: Option<f64>,
+	pub longitude: Option<f64>,
+	pub zip: Option<String>,
+}
+
+impl Home {
+	/// `None` until the coordinates have been looked up
+	pub fn coordinates(&self) -> Option<Coordinates> {
+		self.latitude
+			.zip(self.longitude)
+			.map(|(latitude, longitude)| Coordinates {
+				latitude,
+				longitude,
+			})
+	}
+}
+
+/// roughly how long the drive is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Driving {
+	/// under 2 hours
+	Short,
+	/// 2 to 6 hours
+	HalfDay,
+	/// 6 to 12 hours
+	FullDay,
+	/// over 12 hours
+	NotPractical,
+}
+
+/// roughly what kind of flight it is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Flying {
+	/// under 300 km
+	NotNeeded,
+	/// under 1100 km
+	ShortHaul,
+	/// under 4100 km
+	MediumHaul,
+	LongHaul,
+}
+
+impl Driving {
+	pub fn from_km(km: f64) -> Self {
+		match km * ROAD_FACTOR / DRIVING_KM_PER_HOUR {
+			hours if hours < 2.0 => Self::Short,
+			hours if hours < 6.0 => Self::HalfDay,
+			hours if hours < 12.0 => Self::FullDay,
+			_ => Self::NotPractical,
+		}
+	}
+}
+
+impl Flying {
+	pub fn from_km(km: f64) -> Self {
+		match km {
+			km if km < 300.0 => Self::NotNeeded,
+			km if km < 1100.0 => Self::ShortHaul,
+			km if km < 4100.0 => Self::MediumHaul,
+			_ => Self::LongHaul,
+		}
+	}
+}
+
+/// the parts of the weather worth comparing at a glance
+#[derive(Debug, Serialize)]
+pub struct Conditions {
+	pub temperature: f64,
+	pub feels_like: f64,
+	pub weather_type: i64,
+	pub weather_description: String,
+	pub humidity: f64,
+	pub wind_speed: f64,
+}
+
+impl From<&Weather> for Conditions {
+	fn from(weather: &Weather) -> Self {
+		Self {
+			temperature: weather.temperature,
+			feels_like: weather.feels_like,
+			weather_type: weather.weather_type,
+			weather_description: weather.weather_description.clone(),
+			humidity: weather.humidity,
+			wind_speed: weather.wind_speed,
+		}
+	}
+}
+
+/// hours the university's clocks are ahead of home's right now (negative if behind)
+pub fn time_difference_hours(home: &Tz, university: &Tz) -> f64 {
+	let now = Utc::now();
+	let offset = |tz: &Tz| now.with_timezone(tz).offset().fix().local_minus_utc();
+
+	f64::from(offset(university) - offset(home)) / 3600.0
+}
+
+#[derive(Debug, Serialize)]
+pub struct FromHome {
+	pub university_id: i64,
+	pub name: String,
+	/// great-circle distance
+	pub distance_km: f64,
+	pub driving: Driving,
+	pub flying: Flying,
+	pub time_difference_hours: f64,
+	/// the current weather at the university, if it could be loaded
+	pub weather: Option<Conditions>,
+}
+
+impl FromHome {
+	pub fn new(
+		home: &Coordinates,
+		home_tz: &Tz,
+		university: &University,
+		weather: Option<&Weather>,
+	) -> Self {
+		let distance_km = haversine_km(
+			home,
+			&Coordinates {
+				latitude: university.latitude,
+				longitude: university.longitude,
+			},
+		);
+
+		Self {
+			university_id: university.id,
+			name: university.name.clone(),
+			distance_km,
+			driving: Driving::from_km(distance_km),
+			flying: Flying::from_km(distance_km),
+			time_difference_hours: time_difference_hours(home_tz, &university.timezone),
+			weather: weather.map(Into::into),
+		}
+	}
+}
+
+#[derive(Debug, Serialize)]
+pub struct HomeComparison {
+	/// the current weather at home, if it could be loaded
+	pub weather: Option<Conditions>,
+	pub universities: Vec<FromHome>,
+}
@@ -0,0 +1,133 @@
+use std::f64::consts::PI;
+
+use chrono::{DateTime, Datelike, Duration, Timelike, Utc};
+use openweather_api::Coordinates;
+
+use super::forecast::Forecast;
+
+/// steps further apart than this are treated as missing data and are not interpolated across
+const MAX_STEP_HOURS: i64 = 3;
+
+/// elevation of the sun's center at sunrise/sunset, accounting for refraction and the sun's radius
+const SUNRISE_ELEVATION: f64 = -0.833;
+
+#[inline]
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+	a + (b - a) * t
+}
+
+/// solar elevation in degrees, using the NOAA general solar position approximation
+fn solar_elevation(coords: &Coordinates, time: DateTime<Utc>) -> f64 {
+	let hour = time.hour() as f64 + time.minute() as f64 / 60.0 + time.second() as f64 / 3600.0;
+	// fractional year, in radians
+	let gamma = 2.0 * PI / 365.0 * (time.ordinal0() as f64 + (hour - 12.0) / 24.0);
+
+	// in minutes
+	let equation_of_time = 229.18
+		* (0.000075 + 0.001868 * gamma.cos()
+			- 0.032077 * gamma.sin()
+			- 0.014615 * (2.0 * gamma).cos()
+			- 0.040849 * (2.0 * gamma).sin());
+
+	// in radians
+	let declination = 0.006918 - 0.399912 * gamma.cos() + 0.070257 * gamma.sin()
+		- 0.006758 * (2.0 * gamma).cos()
+		+ 0.000907 * (2.0 * gamma).sin()
+		- 0.002697 * (3.0 * gamma).cos()
+		+ 0.00148 * (3.0 * gamma).sin();
+
+	let true_solar_minutes = hour * 60.0 + equation_of_time + 4.0 * coords.longitude;
+	let hour_angle = (true_solar_minutes / 4.0 - 180.0).to_radians();
+	let latitude = coords.latitude.to_radians();
+
+	let cos_zenith =
+		latitude.sin() * declination.sin() + latitude.cos() * declination.cos() * hour_angle.cos();
+
+	90.0 - cos_zenith.clamp(-1.0, 1.0).acos().to_degrees()
+}
+
+/// whether the sun is up at `time` (UTC) at `coords`
+pub fn is_day(coords: &Coordinates, time: DateTime<Utc>) -> bool {
+	solar_elevation(coords, time) > SUNRISE_ELEVATION
+}
+
+/// the forecast `t` of the way from `a` to `b`, for `0 <= t < 1`
+fn between(
+	a: &Forecast,
+	b: &Forecast,
+	time: DateTime<Utc>,
+	t: f64,
+	coords: &Coordinates,
+) -> Forecast {
+	// discrete conditions can't be interpolated, so take them from the closer step
+	let nearest = if t < 0.5 { a } else { b };
+
+	Forecast {
+		university_id: a.university_id,
+		time,
+		fetched_at: nearest.fetched_at,
+		temperature: lerp(a.temperature, b.temperature, t),
+		feels_like: lerp(a.feels_like, b.feels_like, t),
+		weather_id: nearest.weather_id,
+		weather_description: nearest.weather_description.clone(),
+		humidity: lerp(a.humidity, b.humidity, t),
+		pressure: lerp(a.pressure, b.pressure, t),
+		wind_speed: lerp(a.wind_speed, b.wind_speed, t),
+		precipitation_chance: lerp(a.precipitation_chance, b.precipitation_chance, t),
+		// the 3-hour steps only say whether the sun is up at that step,
+		// so sunrise and sunset in between have to be calculated
+		is_day: if t == 0.0 {
+			a.is_day
+		} else {
+			is_day(coords, time)
+		},
+	}
+}
+
+/// linearly interpolates 3-hour forecasts (sorted by time) into hourly forecasts
+pub fn hourly(forecasts: &[Forecast], coords: &Coordinates) -> Vec<Forecast> {
+	let mut hourly = Vec::with_capacity(forecasts.len() * MAX_STEP_HOURS as usize);
+
+	for pair in forecasts.windows(2) {
+		let (a, b) = (&pair[0], &pair[1]);
+		let steps = (b.time - a.time).num_hours();
+
+		if !(1..=MAX_STEP_HOURS).contains(&steps) {
+			hourly.push(between(a, a, a.time, 0.0, coords));
+			continue;
+		}
+
+		for step in 0..steps {
+			let time = a.time + Duration::hours(step);
+			hourly.push(between(a, b, time, step as f64 / steps as f64, coords));
+		}
+	}
+
+	if let Some(last) = forecasts.last() {
+		hourly.push(between(last, last, last.time, 0.0, coords));
+	}
+
+	hourly
+}
+
+/// interpolates the forecast at exactly `time` from forecasts sorted by time
+pub fn at(forecasts: &[Forecast], time: DateTime<Utc>, coords: &Coordinates) -> Option<Forecast> {
+	let idx = forecasts.partition_point(|forecast| forecast.time <= time);
+
+	match (
+		idx.checked_sub(1).map(|i| &forecasts[i]),
+		forecasts.get(idx),
+	) {
+		(Some(a), _) if a.time == time => Some(between(a, a, time, 0.0, coords)),
+		(Some(a), Some(b)) => {
+			let span = (b.time - a.time).num_seconds();
+			if span > MAX_STEP_HOURS * 60 * 60 {
+				return None;
+			}
+
+			let t = (time - a.time).num_seconds() as f64 / span as f64;
+			Some(between(a, b, time, t, coords))
+		}
+		_ => None,
+	}
+}
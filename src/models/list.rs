@@ -0,0 +1,264 @@
+//! named lists of universities, which can be shared with other users.
+//! every user has a default list of the universities they track
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::{Acquire, Executor, Pool};
+
+use super::university::University;
+
+/// the name of the list every user starts with
+pub const DEFAULT_NAME: &str = "My universities";
+
+/// what a member can do with a list, each role able to do everything the ones before it can
+#[derive(
+	Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, sqlx::Type,
+)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum Role {
+	/// can see the list and keep their own notes on it
+	Viewer,
+	/// can rename the list and change its universities
+	Editor,
+	/// can delete the list, share it, and manage its members
+	Owner,
+}
+
+#[derive(Debug, Serialize)]
+pub struct List {
+	pub id: i64,
+	pub name: String,
+	pub is_default: bool,
+	/// the role of whoever loaded the list
+	pub role: Role,
+	/// university ids, in the list's order
+	pub universities: Vec<i64>,
+}
+
+impl List {
+	async fn load_entries(con: impl Executor<'_>, id: i64) -> sqlx::Result<Vec<i64>> {
+		sqlx::query!(
+			"SELECT university_id FROM list_entries WHERE list_id = $1 ORDER BY position, university_id",
+			id
+		)
+		.fetch_all(con)
+		.await
+		.map(|rows| rows.into_iter().map(|row| row.university_id).collect())
+	}
+
+	pub async fn load(
+		con: impl Executor<'_> + Clone,
+		user_id: Uuid,
+		id: i64,
+	) -> sqlx::Result<Option<Self>> {
+		let list = sqlx::query!(
+			r#"SELECT lists.id, lists.name, lists.is_default, m.role AS "role: Role"
+			FROM lists INNER JOIN list_members AS m ON m.list_id = lists.id
+			WHERE lists.id = $1 AND m.user_id = $2 AND m.accepted"#,
+			id,
+			user_id
+		)
+		.fetch_optional(con.clone())
+		.await?;
+
+		match list {
+			Some(list) => Ok(Some(Self {
+				id: list.id,
+				name: list.name,
+				is_default: list.is_default,
+				role: list.role,
+				universities: Self::load_entries(con, list.id).await?,
+			})),
+			None => Ok(None),
+		}
+	}
+
+	/// loads any list, no matter who it belongs to, as a viewer
+	pub async fn load_by_id(con: impl Executor<'_> + Clone, id: i64) -> sqlx::Result<Option<Self>> {
+		let list = sqlx::query!("SELECT id, name, is_default FROM lists WHERE id = $1", id)
+			.fetch_optional(con.clone())
+			.await?;
+
+		match list {
+			Some(list) => Ok(Some(Self {
+				id: list.id,
+				name: list.name,
+				is_default: list.is_default,
+				role: Role::Viewer,
+				universities: Self::load_entries(con, list.id).await?,
+			})),
+			None => Ok(None),
+		}
+	}
+
+	/// every list the user is a member of, their default list first
+	pub async fn load_all(
+		con: impl Executor<'_> + Clone,
+		user_id: Uuid,
+	) -> sqlx::Result<Vec<Self>> {
+		let lists = sqlx::query!(
+			r#"SELECT lists.id, lists.name, lists.is_default, m.role AS "role: Role"
+			FROM lists INNER JOIN list_members AS m ON m.list_id = lists.id
+			WHERE m.user_id = $1 AND m.accepted
+			ORDER BY lists.is_default DESC, lists.id"#,
+			user_id
+		)
+		.fetch_all(con.clone())
+		.await?;
+
+		let mut loaded = Vec::with_capacity(lists.len());
+		for list in lists {
+			loaded.push(Self {
+				id: list.id,
+				name: list.name,
+				is_default: list.is_default,
+				role: list.role,
+				universities: Self::load_entries(con.clone(), list.id).await?,
+			});
+		}
+
+		Ok(loaded)
+	}
+
+	/// adds the user who made a list as its owner
+	async fn add_owner(con: impl Executor<'_>, id: i64, user_id: Uuid) -> sqlx::Result<()> {
+		sqlx::query!(
+			"INSERT INTO list_members (list_id, user_id, role, accepted) VALUES ($1, $2, $3, TRUE)",
+			id,
+			user_id,
+			Role::Owner
+		)
+		.execute(con)
+		.await
+		.map(|_| ())
+	}
+
+	pub async fn create(con: impl Acquire<'_>, user_id: Uuid, name: String) -> sqlx::Result<Self> {
+		let mut trans = con.begin().await?;
+
+		let id = sqlx::query!(
+			"INSERT INTO lists (user_id, name) VALUES ($1, $2) RETURNING id",
+			user_id,
+			name
+		)
+		.fetch_one(&mut trans)
+		.await?
+		.id;
+
+		Self::add_owner(&mut trans, id, user_id).await?;
+		trans.commit().await?;
+
+		Ok(Self {
+			id,
+			name,
+			is_default: false,
+			role: Role::Owner,
+			universities: Vec::new(),
+		})
+	}
+
+	/// creates the user's default list
+	pub async fn create_default(con: impl Acquire<'_>, user_id: Uuid) -> sqlx::Result<()> {
+		let mut trans = con.begin().await?;
+
+		let id = sqlx::query!(
+			"INSERT INTO lists (user_id, name, is_default) VALUES ($1, $2, TRUE) RETURNING id",
+			user_id,
+			DEFAULT_NAME
+		)
+		.fetch_one(&mut trans)
+		.await?
+		.id;
+
+		Self::add_owner(&mut trans, id, user_id).await?;
+		trans.commit().await
+	}
+
+	/// the id of the user's default list
+	pub async fn default_id(con: impl Executor<'_>, user_id: Uuid) -> sqlx::Result<i64> {
+		sqlx::query!(
+			"SELECT id FROM lists WHERE user_id = $1 AND is_default",
+			user_id
+		)
+		.fetch_one(con)
+		.await
+		.map(|row| row.id)
+	}
+
+	pub async fn rename(&mut self, con: impl Executor<'_>, name: String) -> sqlx::Result<()> {
+		sqlx::query!("UPDATE lists SET name = $1 WHERE id = $2", name, self.id)
+			.execute(con)
+			.await?;
+
+		self.name = name;
+		Ok(())
+	}
+
+	pub async fn delete(self, con: impl Executor<'_>) -> sqlx::Result<()> {
+		sqlx::query!("DELETE FROM lists WHERE id = $1", self.id)
+			.execute(con)
+			.await
+			.map(|_| ())
+	}
+
+	/// adds a university to the end of a list, if it isn't already in it
+	pub async fn add(con: impl Executor<'_>, id: i64, university_id: i64) -> sqlx::Result<()> {
+		sqlx::query!(
+			"INSERT INTO list_entries (list_id, university_id, position)
+			VALUES ($1, $2, (SELECT coalesce(MAX(position) + 1, 0) FROM list_entries WHERE list_id = $1))
+			ON CONFLICT (list_id, university_id) DO NOTHING",
+			id,
+			university_id
+		)
+		.execute(con)
+		.await
+		.map(|_| ())
+	}
+
+	/// removes a university from a list, returning whether it was in it
+	pub async fn remove(con: impl Executor<'_>, id: i64, university_id: i64) -> sqlx::Result<bool> {
+		sqlx::query!(
+			"DELETE FROM list_entries WHERE list_id = $1 AND university_id = $2",
+			id,
+			university_id
+		)
+		.execute(con)
+		.await
+		.map(|result| result.rows_affected() > 0)
+	}
+
+	/// sorts the list in the order of `ids`, which must be all of its universities
+	pub async fn reorder(&mut self, con: &Pool, ids: Vec<i64>) -> sqlx::Result<()> {
+		let mut trans = con.begin().await?;
+
+		for (position, university_id) in ids.iter().enumerate() {
+			let position = position as i64;
+			sqlx::query!(
+				"UPDATE list_entries SET position = $1 WHERE list_id = $2 AND university_id = $3",
+				position,
+				self.id,
+				university_id
+			)
+			.execute(&mut trans)
+			.await?;
+		}
+
+		trans.commit().await?;
+		self.universities = ids;
+		Ok(())
+	}
+
+	pub async fn load_universities(
+		&self,
+		con: impl Executor<'_> + Clone,
+	) -> sqlx::Result<Vec<University>> {
+		let mut universities = Vec::with_capacity(self.universities.len());
+		for id in &self.universities {
+			universities.extend(University::load(con.clone(), *id).await?);
+		}
+
+		Ok(universities)
+	}
+}
@@ -0,0 +1,85 @@
+use chrono::{
+	serde::ts_milliseconds, DateTime, Duration, LocalResult, NaiveDateTime, TimeZone, Utc,
+};
+use chrono_tz::Tz;
+use openweather_api::Coordinates;
+use serde::Serialize;
+
+use crate::db::Executor;
+
+use super::{forecast::Forecast, interpolate, university::University, weather::Weather};
+
+/// how far from the requested time an observation can be and still be reported
+const MAX_OBSERVATION_MINUTES: i64 = 45;
+
+/// the weather at a university when its wall clock shows a given local time
+#[derive(Debug, Serialize)]
+pub struct LocalTimeWeather {
+	pub university_id: i64,
+	/// the time (UTC) at which the university's wall clock shows the requested time
+	#[serde(with = "ts_milliseconds")]
+	pub time: DateTime<Utc>,
+	/// the observed weather, if `time` has already happened and was recorded
+	pub weather: Option<Weather>,
+	/// the (interpolated) forecast, if there was no observation
+	pub forecast: Option<Forecast>,
+}
+
+/// converts a wall-clock time in `tz` to UTC.
+/// ambiguous times (when clocks go back) resolve to the earlier one,
+/// and skipped times (when clocks go forward) to the hour after.
+pub fn to_utc(tz: &Tz, local: NaiveDateTime) -> DateTime<Utc> {
+	match tz.from_local_datetime(&local) {
+		LocalResult::Single(time) | LocalResult::Ambiguous(time, _) => time.with_timezone(&Utc),
+		LocalResult::None => to_utc(tz, local + Duration::hours(1)),
+	}
+}
+
+impl LocalTimeWeather {
+	/// loads the weather at `university` when its wall clock shows `local`
+	pub async fn load(
+		con: impl Executor<'_> + Clone,
+		university: &University,
+		local: NaiveDateTime,
+	) -> sqlx::Result<Self> {
+		let time = to_utc(&university.timezone, local);
+
+		let mut this = Self {
+			university_id: university.id,
+			time,
+			weather: None,
+			forecast: None,
+		};
+
+		if time <= Utc::now() {
+			this.weather = Weather::get_closest(
+				con.clone(),
+				university.id,
+				time,
+				Duration::minutes(MAX_OBSERVATION_MINUTES),
+			)
+			.await?;
+
+			if this.weather.is_some() {
+				return Ok(this);
+			}
+		}
+
+		let forecasts = Forecast::get_latest_between(
+			con,
+			university.id,
+			time - Duration::hours(3),
+			time + Duration::hours(3),
+		)
+		.await?;
+
+		let coords = Coordinates {
+			latitude: university.latitude,
+			longitude: university.longitude,
+		};
+
+		this.forecast = interpolate::at(&forecasts, time, &coords);
+
+		Ok(this)
+	}
+}
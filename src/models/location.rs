@@ -0,0 +1,457 @@
+//! places a user wants the weather for that aren't universities, like their home town.
+//! their weather and forecasts are saved as the same `Weather` and `Forecast` as universities',
+//! with the location's id in place of the university's, and sent to clients with `location_id`
+
+use chrono::{serde::ts_milliseconds, DateTime, NaiveDate, Utc};
+use openweather_api::Coordinates;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::db::Executor;
+
+use super::{
+	daily::DailyForecast, forecast::Forecast, local_time::to_utc, timezone::Timezone,
+	weather::Weather,
+};
+
+#[derive(Debug, Serialize)]
+pub struct Location {
+	pub id: i64,
+	pub name: String,
+	/// what the coordinates are called, according to OpenWeather
+	pub geocoded_name: Option<String>,
+	pub latitude: f64,
+	pub longitude: f64,
+	pub timezone: Timezone,
+}
+
+/// a location's `Weather`, as sent to clients
+#[derive(Debug, Serialize)]
+pub struct LocationWeather {
+	pub location_id: i64,
+	#[serde(with = "ts_milliseconds")]
+	pub time: DateTime<Utc>,
+	pub temperature: f64,
+	pub feels_like: f64,
+	pub weather_type: i64,
+	pub weather_description: String,
+	pub humidity: f64,
+	pub pressure: f64,
+	pub wind_speed: f64,
+	pub cloudiness: f64,
+}
+
+impl From<Weather> for LocationWeather {
+	fn from(weather: Weather) -> Self {
+		Self {
+			location_id: weather.university_id,
+			time: weather.time,
+			temperature: weather.temperature,
+			feels_like: weather.feels_like,
+			weather_type: weather.weather_type,
+			weather_description: weather.weather_description,
+			humidity: weather.humidity,
+			pressure: weather.pressure,
+			wind_speed: weather.wind_speed,
+			cloudiness: weather.cloudiness,
+		}
+	}
+}
+
+/// a location's `Forecast`, as sent to clients
+#[derive(Debug, Serialize)]
+pub struct LocationForecast {
+	pub location_id: i64,
+	#[serde(with = "ts_milliseconds")]
+	pub time: DateTime<Utc>,
+	#[serde(with = "ts_milliseconds")]
+	pub fetched_at: DateTime<Utc>,
+	pub temperature: f64,
+	pub feels_like: f64,
+	pub weather_id: i64,
+	pub weather_description: String,
+	pub humidity: f64,
+	pub pressure: f64,
+	pub wind_speed: f64,
+	pub precipitation_chance: f64,
+	pub is_day: bool,
+}
+
+impl From<Forecast> for LocationForecast {
+	fn from(forecast: Forecast) -> Self {
+		Self {
+			location_id: forecast.university_id,
+			time: forecast.time,
+			fetched_at: forecast.fetched_at,
+			temperature: forecast.temperature,
+			feels_like: forecast.feels_like,
+			weather_id: forecast.weather_id,
+			weather_description: forecast.weather_description,
+			humidity: forecast.humidity,
+			pressure: forecast.pressure,
+			wind_speed: forecast.wind_speed,
+			precipitation_chance: forecast.precipitation_chance,
+			is_day: forecast.is_day,
+		}
+	}
+}
+
+/// a location's `DailyForecast`, as sent to clients
+#[derive(Debug, Serialize)]
+pub struct LocationDailyForecast {
+	pub location_id: i64,
+	/// the day, in the location's timezone
+	pub date: NaiveDate,
+	pub high: f64,
+	pub low: f64,
+	pub precipitation_chance: f64,
+	pub weather_id: i64,
+	pub weather_description: String,
+	pub day_icon: String,
+	pub night_icon: String,
+}
+
+impl From<DailyForecast> for LocationDailyForecast {
+	fn from(daily: DailyForecast) -> Self {
+		Self {
+			location_id: daily.university_id,
+			date: daily.date,
+			high: daily.high,
+			low: daily.low,
+			precipitation_chance: daily.precipitation_chance,
+			weather_id: daily.weather_id,
+			weather_description: daily.weather_description,
+			day_icon: daily.day_icon,
+			night_icon: daily.night_icon,
+		}
+	}
+}
+
+impl Location {
+	pub fn coordinates(&self) -> Coordinates {
+		Coordinates {
+			latitude: self.latitude,
+			longitude: self.longitude,
+		}
+	}
+
+	pub async fn load(
+		con: impl Executor<'_>,
+		user_id: Uuid,
+		id: i64,
+	) -> sqlx::Result<Option<Self>> {
+		sqlx::query_as!(
+			Self,
+			r#"SELECT
+				id,
+				name,
+				geocoded_name,
+				latitude,
+				longitude,
+				timezone AS "timezone: Timezone"
+			FROM locations WHERE id = $1 AND user_id = $2 AND NOT is_home"#,
+			id,
+			user_id
+		)
+		.fetch_optional(con)
+		.await
+	}
+
+	pub async fn load_all(con: impl Executor<'_>, user_id: Uuid) -> sqlx::Result<Vec<Self>> {
+		sqlx::query_as!(
+			Self,
+			r#"SELECT
+				id,
+				name,
+				geocoded_name,
+				latitude,
+				longitude,
+				timezone AS "timezone: Timezone"
+			FROM locations WHERE user_id = $1 AND NOT is_home
+			ORDER BY id"#,
+			user_id
+		)
+		.fetch_all(con)
+		.await
+	}
+
+	pub async fn count(con: impl Executor<'_>, user_id: Uuid) -> sqlx::Result<i64> {
+		sqlx::query!(
+			r#"SELECT COUNT(*) AS "count!: i64" FROM locations WHERE user_id = $1 AND NOT is_home"#,
+			user_id
+		)
+		.fetch_one(con)
+		.await
+		.map(|row| row.count)
+	}
+
+	/// the location kept for the user's home, if it's been made yet
+	pub async fn load_home(con: impl Executor<'_>, user_id: Uuid) -> sqlx::Result<Option<Self>> {
+		sqlx::query_as!(
+			Self,
+			r#"SELECT
+				id,
+				name,
+				geocoded_name,
+				latitude,
+				longitude,
+				timezone AS "timezone: Timezone"
+			FROM locations WHERE user_id = $1 AND is_home"#,
+			user_id
+		)
+		.fetch_optional(con)
+		.await
+	}
+
+	/// the user's home location, making it if it doesn't exist yet
+	pub async fn load_or_create_home(
+		con: impl Executor<'_> + Clone,
+		user_id: Uuid,
+		coords: &Coordinates,
+		timezone: Timezone,
+	) -> sqlx::Result<Self> {
+		if let Some(home) = Self::load_home(con.clone(), user_id).await? {
+			return Ok(home);
+		}
+
+		// another request might have just made it
+		sqlx::query!(
+			"INSERT INTO locations (user_id, name, latitude, longitude, timezone, is_home)
+			VALUES ($1, 'Home', $2, $3, $4, TRUE)
+			ON CONFLICT (user_id) WHERE is_home DO NOTHING",
+			user_id,
+			coords.latitude,
+			coords.longitude,
+			timezone
+		)
+		.execute(con.clone())
+		.await?;
+
+		Self::load_home(con, user_id)
+			.await?
+			.ok_or(sqlx::Error::RowNotFound)
+	}
+
+	/// forgets the user's home location and its weather, when their home moves
+	pub async fn delete_home(con: impl Executor<'_>, user_id: Uuid) -> sqlx::Result<()> {
+		sqlx::query!(
+			"DELETE FROM locations WHERE user_id = $1 AND is_home",
+			user_id
+		)
+		.execute(con)
+		.await
+		.map(|_| ())
+	}
+
+	/// every user's locations (including their homes), for the updaters
+	pub async fn load_every(con: impl Executor<'_>) -> sqlx::Result<Vec<Self>> {
+		sqlx::query_as!(
+			Self,
+			r#"SELECT
+				id,
+				name,
+				geocoded_name,
+				latitude,
+				longitude,
+				timezone AS "timezone: Timezone"
+			FROM locations"#
+		)
+		.fetch_all(con)
+		.await
+	}
+
+	pub async fn create(
+		con: impl Executor<'_>,
+		user_id: Uuid,
+		name: String,
+		geocoded_name: Option<String>,
+		coords: &Coordinates,
+		timezone: Timezone,
+	) -> sqlx::Result<Self> {
+		let id = sqlx::query!(
+			"INSERT INTO locations (user_id, name, geocoded_name, latitude, longitude, timezone)
+			VALUES ($1, $2, $3, $4, $5, $6)
+			RETURNING id",
+			user_id,
+			name,
+			geocoded_name,
+			coords.latitude,
+			coords.longitude,
+			timezone
+		)
+		.fetch_one(con)
+		.await?
+		.id;
+
+		Ok(Self {
+			id,
+			name,
+			geocoded_name,
+			latitude: coords.latitude,
+			longitude: coords.longitude,
+			timezone,
+		})
+	}
+
+	pub async fn rename(&mut self, con: impl Executor<'_>, name: String) -> sqlx::Result<()> {
+		sqlx::query!(
+			"UPDATE locations SET name = $1 WHERE id = $2",
+			name,
+			self.id
+		)
+		.execute(con)
+		.await?;
+
+		self.name = name;
+		Ok(())
+	}
+
+	pub async fn delete(self, con: impl Executor<'_>) -> sqlx::Result<()> {
+		sqlx::query!("DELETE FROM locations WHERE id = $1", self.id)
+			.execute(con)
+			.await
+			.map(|_| ())
+	}
+
+	/// the most recent weather, if there is any
+	pub async fn get_weather(&self, con: impl Executor<'_>) -> sqlx::Result<Option<Weather>> {
+		sqlx::query_as!(
+			Weather,
+			r#"SELECT
+				location_id AS university_id,
+				time AS "time: DateTime<Utc>",
+				temperature,
+				feels_like,
+				weather_type,
+				weather_description,
+				humidity,
+				pressure,
+				wind_speed,
+				cloudiness
+			FROM location_weather
+			WHERE location_id = $1
+			ORDER BY time DESC
+			LIMIT 1"#,
+			self.id
+		)
+		.fetch_optional(con)
+		.await
+	}
+
+	/// saves weather fetched with the location's id
+	pub async fn put_weather(con: impl Executor<'_>, weather: &Weather) -> sqlx::Result<()> {
+		// timestamps are stored as naive UTC
+		let time = weather.time.naive_utc();
+		sqlx::query!(
+			"INSERT INTO location_weather (
+				location_id,
+				time,
+				temperature,
+				feels_like,
+				weather_type,
+				weather_description,
+				humidity,
+				pressure,
+				wind_speed,
+				cloudiness
+			) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+			ON CONFLICT (location_id, time) DO NOTHING",
+			weather.university_id,
+			time,
+			weather.temperature,
+			weather.feels_like,
+			weather.weather_type,
+			weather.weather_description,
+			weather.humidity,
+			weather.pressure,
+			weather.wind_speed,
+			weather.cloudiness
+		)
+		.execute(con)
+		.await
+		.map(|_| ())
+	}
+
+	/// forecasts from the start of today (in the location's timezone) on
+	pub async fn get_forecasts(&self, con: impl Executor<'_>) -> sqlx::Result<Vec<Forecast>> {
+		// unlike US universities, locations can be where DST starts at midnight,
+		// so midnight might not exist
+		let today = Utc::now()
+			.with_timezone(&*self.timezone)
+			.date()
+			.naive_local();
+		let not_before = to_utc(&self.timezone, today.and_hms(0, 0, 0)).naive_utc();
+
+		sqlx::query_as!(
+			Forecast,
+			r#"SELECT
+				location_id AS university_id,
+				time AS "time: DateTime<Utc>",
+				fetched_at AS "fetched_at: DateTime<Utc>",
+				temperature,
+				feels_like,
+				weather_id,
+				weather_description,
+				humidity,
+				pressure,
+				wind_speed,
+				precipitation_chance,
+				is_day
+			FROM location_forecasts
+			WHERE location_id = $1 AND time >= $2
+			ORDER BY time"#,
+			self.id,
+			not_before
+		)
+		.fetch_all(con)
+		.await
+	}
+
+	/// saves a forecast fetched with the location's id, replacing any older one for the same time
+	pub async fn put_forecast(con: impl Executor<'_>, forecast: &Forecast) -> sqlx::Result<()> {
+		// timestamps are stored as naive UTC
+		let (time, fetched_at) = (forecast.time.naive_utc(), forecast.fetched_at.naive_utc());
+		sqlx::query!(
+			"INSERT INTO location_forecasts (
+				location_id,
+				time,
+				fetched_at,
+				temperature,
+				feels_like,
+				weather_id,
+				weather_description,
+				humidity,
+				pressure,
+				wind_speed,
+				precipitation_chance,
+				is_day
+			) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+			ON CONFLICT (location_id, time) DO UPDATE SET
+				fetched_at = excluded.fetched_at,
+				temperature = excluded.temperature,
+				feels_like = excluded.feels_like,
+				weather_id = excluded.weather_id,
+				weather_description = excluded.weather_description,
+				humidity = excluded.humidity,
+				pressure = excluded.pressure,
+				wind_speed = excluded.wind_speed,
+				precipitation_chance = excluded.precipitation_chance,
+				is_day = excluded.is_day",
+			forecast.university_id,
+			time,
+			fetched_at,
+			forecast.temperature,
+			forecast.feels_like,
+			forecast.weather_id,
+			forecast.weather_description,
+			forecast.humidity,
+			forecast.pressure,
+			forecast.wind_speed,
+			forecast.precipitation_chance,
+			forecast.is_day
+		)
+		.execute(con)
+		.await
+		.map(|_| ())
+	}
+}
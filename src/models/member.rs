@@ -0,0 +1,227 @@
+//! the members of a shared list, their invitations, and their notes
+
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::db::Executor;
+
+use super::list::Role;
+
+#[derive(Debug, Serialize)]
+pub struct Member {
+	pub username: String,
+	pub role: Role,
+	/// `false` until they accept their invitation
+	pub accepted: bool,
+}
+
+/// a list someone has been invited to
+#[derive(Debug, Serialize)]
+pub struct Invitation {
+	pub list_id: i64,
+	pub name: String,
+	pub role: Role,
+	/// the username of the list's owner
+	pub owner: String,
+}
+
+/// a member's notes on one of the universities in a list
+#[derive(Debug, Serialize)]
+pub struct MemberNote {
+	pub university_id: i64,
+	pub username: String,
+	pub notes: String,
+}
+
+impl Member {
+	/// everyone in a list, including those who haven't accepted yet, owner first
+	pub async fn load_all(con: impl Executor<'_>, list_id: i64) -> sqlx::Result<Vec<Self>> {
+		sqlx::query_as!(
+			Self,
+			r#"SELECT users.username, m.role AS "role: Role", m.accepted
+			FROM list_members AS m INNER JOIN users ON users.id = m.user_id
+			WHERE m.list_id = $1
+			ORDER BY m.role = 'owner' DESC, users.username"#,
+			list_id
+		)
+		.fetch_all(con)
+		.await
+	}
+
+	/// the id and role of a member of the list, by username
+	pub async fn find(
+		con: impl Executor<'_>,
+		list_id: i64,
+		username: &str,
+	) -> sqlx::Result<Option<(Uuid, Role)>> {
+		sqlx::query!(
+			r#"SELECT m.user_id AS "user_id: Uuid", m.role AS "role: Role"
+			FROM list_members AS m INNER JOIN users ON users.id = m.user_id
+			WHERE m.list_id = $1 AND users.username = $2"#,
+			list_id,
+			username
+		)
+		.fetch_optional(con)
+		.await
+		.map(|row| row.map(|row| (row.user_id, row.role)))
+	}
+
+	/// invites a user, who isn't a member until they accept
+	pub async fn invite(
+		con: impl Executor<'_>,
+		list_id: i64,
+		user_id: Uuid,
+		role: Role,
+	) -> sqlx::Result<()> {
+		sqlx::query!(
+			"INSERT INTO list_members (list_id, user_id, role) VALUES ($1, $2, $3)",
+			list_id,
+			user_id,
+			role
+		)
+		.execute(con)
+		.await
+		.map(|_| ())
+	}
+
+	pub async fn set_role(
+		con: impl Executor<'_>,
+		list_id: i64,
+		user_id: Uuid,
+		role: Role,
+	) -> sqlx::Result<()> {
+		sqlx::query!(
+			"UPDATE list_members SET role = $1 WHERE list_id = $2 AND user_id = $3",
+			role,
+			list_id,
+			user_id
+		)
+		.execute(con)
+		.await
+		.map(|_| ())
+	}
+
+	/// removes a member and their notes, returning whether they were a member
+	pub async fn remove(
+		con: impl Executor<'_> + Clone,
+		list_id: i64,
+		user_id: Uuid,
+	) -> sqlx::Result<bool> {
+		sqlx::query!(
+			"DELETE FROM list_notes WHERE list_id = $1 AND user_id = $2",
+			list_id,
+			user_id
+		)
+		.execute(con.clone())
+		.await?;
+
+		sqlx::query!(
+			"DELETE FROM list_members WHERE list_id = $1 AND user_id = $2",
+			list_id,
+			user_id
+		)
+		.execute(con)
+		.await
+		.map(|result| result.rows_affected() > 0)
+	}
+}
+
+impl Invitation {
+	/// the user's invitations they haven't accepted or declined
+	pub async fn load_all(con: impl Executor<'_>, user_id: Uuid) -> sqlx::Result<Vec<Self>> {
+		sqlx::query_as!(
+			Self,
+			r#"SELECT
+				lists.id AS list_id,
+				lists.name,
+				m.role AS "role: Role",
+				users.username AS owner
+			FROM list_members AS m
+			INNER JOIN lists ON lists.id = m.list_id
+			INNER JOIN users ON users.id = lists.user_id
+			WHERE m.user_id = $1 AND NOT m.accepted
+			ORDER BY lists.id"#,
+			user_id
+		)
+		.fetch_all(con)
+		.await
+	}
+
+	/// accepts an invitation, returning whether there was one
+	pub async fn accept(con: impl Executor<'_>, list_id: i64, user_id: Uuid) -> sqlx::Result<bool> {
+		sqlx::query!(
+			"UPDATE list_members SET accepted = TRUE
+			WHERE list_id = $1 AND user_id = $2 AND NOT accepted",
+			list_id,
+			user_id
+		)
+		.execute(con)
+		.await
+		.map(|result| result.rows_affected() > 0)
+	}
+
+	/// declines an invitation, returning whether there was one
+	pub async fn decline(
+		con: impl Executor<'_>,
+		list_id: i64,
+		user_id: Uuid,
+	) -> sqlx::Result<bool> {
+		sqlx::query!(
+			"DELETE FROM list_members WHERE list_id = $1 AND user_id = $2 AND NOT accepted",
+			list_id,
+			user_id
+		)
+		.execute(con)
+		.await
+		.map(|result| result.rows_affected() > 0)
+	}
+}
+
+impl MemberNote {
+	/// every member's notes on the list's universities
+	pub async fn load_all(con: impl Executor<'_>, list_id: i64) -> sqlx::Result<Vec<Self>> {
+		sqlx::query_as!(
+			Self,
+			"SELECT n.university_id, users.username, n.notes
+			FROM list_notes AS n INNER JOIN users ON users.id = n.user_id
+			WHERE n.list_id = $1
+			ORDER BY n.university_id, users.username",
+			list_id
+		)
+		.fetch_all(con)
+		.await
+	}
+
+	/// sets the member's notes on a university in the list, or clears them if `None`
+	pub async fn put(
+		con: impl Executor<'_>,
+		list_id: i64,
+		university_id: i64,
+		user_id: Uuid,
+		notes: Option<&str>,
+	) -> sqlx::Result<()> {
+		match notes {
+			Some(notes) => sqlx::query!(
+				"INSERT INTO list_notes (list_id, university_id, user_id, notes)
+				VALUES ($1, $2, $3, $4)
+				ON CONFLICT (list_id, university_id, user_id) DO UPDATE SET notes = excluded.notes",
+				list_id,
+				university_id,
+				user_id,
+				notes
+			)
+			.execute(con)
+			.await
+			.map(|_| ()),
+			None => sqlx::query!(
+				"DELETE FROM list_notes WHERE list_id = $1 AND university_id = $2 AND user_id = $3",
+				list_id,
+				university_id,
+				user_id
+			)
+			.execute(con)
+			.await
+			.map(|_| ()),
+		}
+	}
+}
@@ -1,4 +1,28 @@
+pub mod accuracy;
+pub mod admissions;
+pub mod browse;
+pub mod checklist;
+pub mod climatology;
+pub mod compare;
+pub mod daily;
+pub mod deadline;
+pub mod export;
+pub mod fit;
 pub mod forecast;
+pub mod home;
+pub mod interpolate;
+pub mod list;
+pub mod local_time;
+pub mod location;
+pub mod member;
+pub mod nearby;
+pub mod outcomes;
+pub mod profile;
+pub mod search;
+pub mod share;
+pub mod stats;
+pub mod timezone;
+pub mod tracked;
 pub mod university;
 pub mod user;
 pub mod weather;
@@ -0,0 +1,69 @@
+use openweather_api::Coordinates;
+use serde::Serialize;
+
+use crate::{
+	db::Executor,
+	geo::distance::{haversine_km, BoundingBox},
+};
+
+/// a university and how far it is from somewhere
+#[derive(Debug, Serialize)]
+pub struct Nearby {
+	pub id: i64,
+	pub name: String,
+	pub city: String,
+	pub state: String,
+	pub latitude: f64,
+	pub longitude: f64,
+	/// great-circle distance, in kilometers
+	pub distance_km: f64,
+}
+
+impl Nearby {
+	/// the (up to) `limit` closest universities within `radius_km` of `center`, closest first
+	pub async fn load(
+		con: impl Executor<'_>,
+		center: &Coordinates,
+		radius_km: f64,
+		limit: usize,
+	) -> sqlx::Result<Vec<Self>> {
+		let bounds = BoundingBox::around(center, radius_km);
+
+		// the box is bigger than the circle, so filter out the corners after
+		let candidates = sqlx::query!(
+			"SELECT id, name, city, state, latitude, longitude FROM universities
+			WHERE latitude BETWEEN $1 AND $2 AND longitude BETWEEN $3 AND $4",
+			bounds.min_latitude,
+			bounds.max_latitude,
+			bounds.min_longitude,
+			bounds.max_longitude
+		)
+		.fetch_all(con)
+		.await?;
+
+		let mut nearby: Vec<_> = candidates
+			.into_iter()
+			.map(|row| Self {
+				distance_km: haversine_km(
+					center,
+					&Coordinates {
+						latitude: row.latitude,
+						longitude: row.longitude,
+					},
+				),
+				id: row.id,
+				name: row.name,
+				city: row.city,
+				state: row.state,
+				latitude: row.latitude,
+				longitude: row.longitude,
+			})
+			.filter(|university| university.distance_km <= radius_km)
+			.collect();
+
+		nearby.sort_by(|a, b| a.distance_km.total_cmp(&b.distance_km));
+		nearby.truncate(limit);
+
+		Ok(nearby)
+	}
+}
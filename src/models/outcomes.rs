@@ -0,0 +1,80 @@
+use serde::Serialize;
+
+use crate::db::Executor;
+
+/// what happens to a university's students, from the College Scorecard
+#[derive(Debug, Serialize)]
+pub struct Outcomes {
+	pub university_id: i64,
+	/// median earnings of former students 10 years after they started
+	pub median_earnings: Option<i64>,
+	/// median federal loan debt of students who completed
+	pub median_debt: Option<f64>,
+	/// average net price (cost after grants) for families making $0-30,000 a year
+	pub net_price_0_30000: Option<i64>,
+	pub net_price_30001_48000: Option<i64>,
+	pub net_price_48001_75000: Option<i64>,
+	pub net_price_75001_110000: Option<i64>,
+	pub net_price_110001_plus: Option<i64>,
+	/// fraction of full-time students who completed within 150% of the normal time
+	pub completion_rate: Option<f64>,
+}
+
+impl Outcomes {
+	pub async fn put(&self, con: impl Executor<'_>) -> sqlx::Result<()> {
+		sqlx::query!(
+			"INSERT INTO university_outcomes (
+				university_id,
+				median_earnings,
+				median_debt,
+				net_price_0_30000,
+				net_price_30001_48000,
+				net_price_48001_75000,
+				net_price_75001_110000,
+				net_price_110001_plus,
+				completion_rate
+			) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+			ON CONFLICT(university_id) DO UPDATE SET (
+				median_earnings,
+				median_debt,
+				net_price_0_30000,
+				net_price_30001_48000,
+				net_price_48001_75000,
+				net_price_75001_110000,
+				net_price_110001_plus,
+				completion_rate
+			) = (
+				excluded.median_earnings,
+				excluded.median_debt,
+				excluded.net_price_0_30000,
+				excluded.net_price_30001_48000,
+				excluded.net_price_48001_75000,
+				excluded.net_price_75001_110000,
+				excluded.net_price_110001_plus,
+				excluded.completion_rate
+			)",
+			self.university_id,
+			self.median_earnings,
+			self.median_debt,
+			self.net_price_0_30000,
+			self.net_price_30001_48000,
+			self.net_price_48001_75000,
+			self.net_price_75001_110000,
+			self.net_price_110001_plus,
+			self.completion_rate
+		)
+		.execute(con)
+		.await
+		.map(|_| ())
+	}
+
+	pub async fn load(con: impl Executor<'_>, university_id: i64) -> sqlx::Result<Option<Self>> {
+		sqlx::query_as!(
+			Self,
+			"SELECT * FROM university_outcomes WHERE university_id = $1",
+			university_id
+		)
+		.fetch_optional(con)
+		.await
+	}
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::Executor;
+
+/// a user's own grades and test scores
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AdmissionsProfile {
+	/// unweighted, out of 4.0
+	pub gpa: Option<f64>,
+	/// SAT evidence-based reading and writing
+	pub sat_english: Option<i64>,
+	pub sat_math: Option<i64>,
+	pub act_composite: Option<i64>,
+	pub act_english: Option<i64>,
+	pub act_math: Option<i64>,
+}
+
+impl AdmissionsProfile {
+	/// checks that every score is possible, returning what's wrong if not
+	pub fn validate(&self) -> Result<(), &'static str> {
+		let in_range =
+			|score: Option<i64>, min, max| score.is_none_or(|s| (min..=max).contains(&s));
+
+		if !self.gpa.is_none_or(|gpa| (0.0..=4.0).contains(&gpa)) {
+			Err("gpa must be between 0 and 4")
+		} else if !in_range(self.sat_english, 200, 800) || !in_range(self.sat_math, 200, 800) {
+			Err("SAT section scores must be between 200 and 800")
+		} else if !in_range(self.act_composite, 1, 36)
+			|| !in_range(self.act_english, 1, 36)
+			|| !in_range(self.act_math, 1, 36)
+		{
+			Err("ACT scores must be between 1 and 36")
+		} else {
+			Ok(())
+		}
+	}
+
+	pub async fn load(con: impl Executor<'_>, user_id: Uuid) -> sqlx::Result<Option<Self>> {
+		sqlx::query_as!(
+			Self,
+			"SELECT gpa, sat_english, sat_math, act_composite, act_english, act_math
+			FROM admissions_profiles WHERE user_id = $1",
+			user_id
+		)
+		.fetch_optional(con)
+		.await
+	}
+
+	pub async fn put(&self, con: impl Executor<'_>, user_id: Uuid) -> sqlx::Result<()> {
+		sqlx::query!(
+			"INSERT INTO admissions_profiles (
+				user_id,
+				gpa,
+				sat_english,
+				sat_math,
+				act_composite,
+				act_english,
+				act_math
+			) VALUES ($1, $2, $3, $4, $5, $6, $7)
+			ON CONFLICT(user_id) DO UPDATE SET (
+				gpa,
+				sat_english,
+				sat_math,
+				act_composite,
+				act_english,
+				act_math
+			) = (
+				excluded.gpa,
+				excluded.sat_english,
+				excluded.sat_math,
+				excluded.act_composite,
+				excluded.act_english,
+				excluded.act_math
+			)",
+			user_id,
+			self.gpa,
+			self.sat_english,
+			self.sat_math,
+			self.act_composite,
+			self.act_english,
+			self.act_math
+		)
+		.execute(con)
+		.await
+		.map(|_| ())
+	}
+}
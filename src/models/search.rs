@@ -0,0 +1,259 @@
+//! ranked university search, by name, alias, city, state or acronym.
+//! in sqlite, this uses the FTS5 indices from `migrations/019_university_search.sql`
+
+use serde::Serialize;
+
+use crate::db::{Executor, Pool};
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct SearchResult {
+	pub id: i64,
+	pub name: String,
+	pub city: String,
+	pub state: String,
+}
+
+/// words that are left out of acronyms (University *of* Southern California => USC)
+const ACRONYM_STOP_WORDS: &[&str] = &["of", "the", "and", "at", "in", "for", "&"];
+
+/// the initials of a university's name, e.g., MIT for Massachusetts Institute of Technology.
+/// IPEDS names branches like `Name-Campus`, so UCLA is University of California-Los Angeles
+pub fn acronym(name: &str) -> String {
+	name.trim_end_matches("-Main Campus")
+		.split(|c: char| c.is_whitespace() || c == '-' || c == '/' || c == ',')
+		.filter(|word| !ACRONYM_STOP_WORDS.contains(&word.to_lowercase().as_str()))
+		.filter_map(|word| word.chars().find(|c| c.is_alphanumeric()))
+		.flat_map(char::to_uppercase)
+		.collect()
+}
+
+/// sets the acronym of universities from before acronyms were stored
+pub async fn backfill_acronyms(con: &Pool) -> sqlx::Result<()> {
+	let universities = sqlx::query!("SELECT id, name FROM universities WHERE acronym IS NULL")
+		.fetch_all(con)
+		.await?;
+
+	if universities.is_empty() {
+		return Ok(());
+	}
+
+	log::info!("Setting acronyms of {} universities", universities.len());
+
+	let mut trans = con.begin().await?;
+
+	for row in universities {
+		let acronym = acronym(&row.name);
+		sqlx::query!(
+			"UPDATE universities SET acronym = $1 WHERE id = $2",
+			acronym,
+			row.id
+		)
+		.execute(&mut trans)
+		.await?;
+	}
+
+	trans.commit().await
+}
+
+/// the words in a search, lowercased, without any punctuation or query syntax
+fn words(search: &str) -> Vec<String> {
+	search
+		.split(|c: char| !c.is_alphanumeric())
+		.filter(|word| !word.is_empty())
+		.map(str::to_lowercase)
+		.collect()
+}
+
+/// every word has to match, and the words can be the start of a longer word (as they're typed)
+#[cfg(feature = "sqlite")]
+fn prefix_query(words: &[String]) -> String {
+	words
+		.iter()
+		.map(|word| format!("\"{}\"*", word))
+		.collect::<Vec<_>>()
+		.join(" ")
+}
+
+/// matches names sharing any 3-character substring with the search,
+/// so the ones with the most in common (i.e., the fewest typos) rank highest
+#[cfg(feature = "sqlite")]
+fn trigram_query(words: &[String]) -> String {
+	let mut trigrams = Vec::new();
+
+	for word in words {
+		let chars: Vec<_> = word.chars().collect();
+		for window in chars.windows(3) {
+			let trigram: String = window.iter().collect();
+			if !trigrams.contains(&trigram) {
+				trigrams.push(trigram);
+			}
+		}
+	}
+
+	trigrams
+		.iter()
+		.map(|trigram| format!("\"{}\"", trigram))
+		.collect::<Vec<_>>()
+		.join(" OR ")
+}
+
+/// searches for universities, best match first.
+/// if nothing matches exactly, falls back to names that are close to the search
+#[cfg(feature = "sqlite")]
+pub async fn search(
+	con: impl Executor<'_> + Clone,
+	search: &str,
+	limit: i64,
+	offset: i64,
+) -> sqlx::Result<Vec<SearchResult>> {
+	let words = words(search);
+	if words.is_empty() {
+		return Ok(Vec::new());
+	}
+
+	// matches in the name or acronym are worth the most, then aliases, then location
+	let query = prefix_query(&words);
+	let results = sqlx::query_as!(
+		SearchResult,
+		r#"SELECT u.id AS "id!", u.name AS "name!", u.city AS "city!", u.state AS "state!"
+		FROM universities_search AS s
+		JOIN universities AS u ON u.id = s.rowid
+		WHERE universities_search MATCH $1
+		ORDER BY bm25(universities_search, 10.0, 5.0, 1.0, 1.0, 10.0)
+		LIMIT $2 OFFSET $3"#,
+		query,
+		limit,
+		offset
+	)
+	.fetch_all(con.clone())
+	.await?;
+
+	if !results.is_empty() {
+		return Ok(results);
+	}
+
+	// past the last page of exact matches, not a typo
+	if offset > 0 {
+		let any = sqlx::query!(
+			"SELECT rowid FROM universities_search WHERE universities_search MATCH $1 LIMIT 1",
+			query
+		)
+		.fetch_optional(con.clone())
+		.await?;
+
+		if any.is_some() {
+			return Ok(results);
+		}
+	}
+
+	let query = trigram_query(&words);
+	if query.is_empty() {
+		return Ok(results);
+	}
+
+	sqlx::query_as!(
+		SearchResult,
+		r#"SELECT u.id AS "id!", u.name AS "name!", u.city AS "city!", u.state AS "state!"
+		FROM universities_fuzzy AS f
+		JOIN universities AS u ON u.id = f.rowid
+		WHERE universities_fuzzy MATCH $1
+		ORDER BY bm25(universities_fuzzy, 2.0, 1.0)
+		LIMIT $2 OFFSET $3"#,
+		query,
+		limit,
+		offset
+	)
+	.fetch_all(con)
+	.await
+}
+
+/// searches for universities, best match first.
+/// the fallback for typos needs the `pg_trgm` extension
+#[cfg(feature = "postgres")]
+pub async fn search(
+	con: impl Executor<'_> + Clone,
+	search: &str,
+	limit: i64,
+	offset: i64,
+) -> sqlx::Result<Vec<SearchResult>> {
+	let words = words(search);
+	if words.is_empty() {
+		return Ok(Vec::new());
+	}
+
+	let query = words
+		.iter()
+		.map(|word| format!("{}:*", word))
+		.collect::<Vec<_>>()
+		.join(" & ");
+
+	let results = sqlx::query_as(
+		"SELECT id, name, city, state
+		FROM universities, to_tsquery('simple', $1) AS query
+		WHERE document @@ query
+		ORDER BY ts_rank(document, query) DESC
+		LIMIT $2 OFFSET $3",
+	)
+	.bind(&query)
+	.bind(limit)
+	.bind(offset)
+	.fetch_all(con.clone())
+	.await?;
+
+	if !results.is_empty() {
+		return Ok(results);
+	}
+
+	// past the last page of exact matches, not a typo
+	if offset > 0 {
+		let any: Option<(i64,)> = sqlx::query_as(
+			"SELECT id FROM universities WHERE document @@ to_tsquery('simple', $1) LIMIT 1",
+		)
+		.bind(&query)
+		.fetch_optional(con.clone())
+		.await?;
+
+		if any.is_some() {
+			return Ok(results);
+		}
+	}
+
+	sqlx::query_as(
+		"SELECT id, name, city, state
+		FROM universities
+		WHERE name % $1 OR aliases % $1
+		ORDER BY greatest(similarity(name, $1), similarity(coalesce(aliases, ''), $1)) DESC
+		LIMIT $2 OFFSET $3",
+	)
+	.bind(words.join(" "))
+	.bind(limit)
+	.bind(offset)
+	.fetch_all(con)
+	.await
+}
+
+/// postgres has no FTS5, so instead of the tables in `migrations/019_university_search.sql`,
+/// the search document is a stored column with a GIN index, and typos use trigram indices
+#[cfg(feature = "postgres")]
+pub async fn create_indices(con: &Pool) -> sqlx::Result<()> {
+	let statements = [
+		"CREATE EXTENSION IF NOT EXISTS pg_trgm",
+		// matches in the name or acronym are worth the most, then aliases, then location
+		"ALTER TABLE universities ADD COLUMN IF NOT EXISTS document tsvector
+		GENERATED ALWAYS AS (
+			setweight(to_tsvector('simple', name), 'A')
+			|| setweight(to_tsvector('simple', coalesce(acronym, '')), 'A')
+			|| setweight(to_tsvector('simple', coalesce(aliases, '')), 'B')
+			|| setweight(to_tsvector('simple', city || ' ' || state), 'C')
+		) STORED",
+		"CREATE INDEX IF NOT EXISTS universities_document ON universities USING GIN (document)",
+		"CREATE INDEX IF NOT EXISTS universities_name_trgm ON universities USING GIN (name gin_trgm_ops)",
+		"CREATE INDEX IF NOT EXISTS universities_aliases_trgm ON universities USING GIN (aliases gin_trgm_ops)",
+	];
+
+	for statement in statements {
+		sqlx::query(statement).execute(con).await?;
+	}
+
+	Ok(())
+}
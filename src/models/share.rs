@@ -0,0 +1,105 @@
+//! read-only links to a list, for people without an account
+
+use chrono::{
+	serde::{ts_milliseconds, ts_milliseconds_option},
+	DateTime, Utc,
+};
+use rand::{distributions::Alphanumeric, Rng};
+use serde::Serialize;
+
+use crate::db::Executor;
+
+/// 62^32 possible tokens is plenty to not be guessed
+const TOKEN_LENGTH: usize = 32;
+
+#[derive(Debug, Serialize)]
+pub struct ShareLink {
+	pub token: String,
+	pub list_id: i64,
+	#[serde(with = "ts_milliseconds")]
+	pub created_at: DateTime<Utc>,
+	/// `null` if it never expires
+	#[serde(with = "ts_milliseconds_option")]
+	pub expires_at: Option<DateTime<Utc>>,
+	pub revoked: bool,
+}
+
+impl ShareLink {
+	pub async fn create(
+		con: impl Executor<'_>,
+		list_id: i64,
+		expires_at: Option<DateTime<Utc>>,
+	) -> sqlx::Result<Self> {
+		let token: String = rand::thread_rng()
+			.sample_iter(&Alphanumeric)
+			.take(TOKEN_LENGTH)
+			.map(char::from)
+			.collect();
+		let created_at = Utc::now();
+
+		// timestamps are stored as naive UTC
+		let created = created_at.naive_utc();
+		let expires = expires_at.map(|time| time.naive_utc());
+		sqlx::query!(
+			"INSERT INTO share_links (token, list_id, created_at, expires_at) VALUES ($1, $2, $3, $4)",
+			token,
+			list_id,
+			created,
+			expires
+		)
+		.execute(con)
+		.await?;
+
+		Ok(Self {
+			token,
+			list_id,
+			created_at,
+			expires_at,
+			revoked: false,
+		})
+	}
+
+	/// every link to a list, including revoked and expired ones, newest first
+	pub async fn load_all(con: impl Executor<'_>, list_id: i64) -> sqlx::Result<Vec<Self>> {
+		sqlx::query_as!(
+			Self,
+			r#"SELECT
+				token,
+				list_id,
+				created_at AS "created_at: DateTime<Utc>",
+				expires_at AS "expires_at: DateTime<Utc>",
+				revoked
+			FROM share_links WHERE list_id = $1
+			ORDER BY created_at DESC"#,
+			list_id
+		)
+		.fetch_all(con)
+		.await
+	}
+
+	/// stops a link from working, returning whether it exists
+	pub async fn revoke(con: impl Executor<'_>, list_id: i64, token: &str) -> sqlx::Result<bool> {
+		sqlx::query!(
+			"UPDATE share_links SET revoked = TRUE WHERE list_id = $1 AND token = $2",
+			list_id,
+			token
+		)
+		.execute(con)
+		.await
+		.map(|result| result.rows_affected() > 0)
+	}
+
+	/// the list a link is to, if it's still valid
+	pub async fn resolve(con: impl Executor<'_>, token: &str) -> sqlx::Result<Option<i64>> {
+		let now = Utc::now().naive_utc();
+		sqlx::query!(
+			"SELECT list_id FROM share_links
+			WHERE token = $1 AND NOT revoked AND (expires_at IS NULL OR expires_at > $2)",
+			token,
+			now
+		)
+		.fetch_optional(con)
+		.await
+		.map(|row| row.map(|row| row.list_id))
+	}
+}
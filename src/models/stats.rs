@@ -0,0 +1,221 @@
+use serde::Serialize;
+
+use crate::db::Executor;
+
+use super::admissions::{percent, sat_composite};
+
+/// one year of IPEDS statistics for a university
+#[derive(Debug, Serialize)]
+pub struct Stats {
+	pub university_id: i64,
+	/// the fall the statistics are from (e.g., 2020 for 2020-21)
+	pub year: i64,
+	pub total_enrollment: Option<i64>,
+	pub undergrad_enrollment: Option<i64>,
+	pub student_to_faculty: Option<i64>,
+	pub graduation_rate: Option<i64>,
+	pub total_applicants: Option<i64>,
+	pub total_admissions: Option<i64>,
+	pub total_enrolled_applicants: Option<i64>,
+	pub admissions_yield: Option<i64>,
+	pub submitted_sat: Option<i64>,
+	pub submitted_act: Option<i64>,
+	pub sat_english_1q: Option<i64>,
+	pub sat_english_3q: Option<i64>,
+	pub sat_math_1q: Option<i64>,
+	pub sat_math_3q: Option<i64>,
+	pub act_composite_1q: Option<i64>,
+	pub act_composite_3q: Option<i64>,
+	pub act_english_1q: Option<i64>,
+	pub act_english_3q: Option<i64>,
+	pub act_math_1q: Option<i64>,
+	pub act_math_3q: Option<i64>,
+	pub application_fee: Option<i64>,
+	pub price_in_district: Option<i64>,
+	pub price_in_state: Option<i64>,
+	pub price_out_of_state: Option<i64>,
+}
+
+impl Stats {
+	pub async fn put(&self, con: impl Executor<'_>) -> sqlx::Result<()> {
+		sqlx::query!(
+			"INSERT INTO university_stats (
+				university_id,
+				year,
+				total_enrollment,
+				undergrad_enrollment,
+				student_to_faculty,
+				graduation_rate,
+				total_applicants,
+				total_admissions,
+				total_enrolled_applicants,
+				admissions_yield,
+				submitted_sat,
+				submitted_act,
+				sat_english_1q,
+				sat_english_3q,
+				sat_math_1q,
+				sat_math_3q,
+				act_composite_1q,
+				act_composite_3q,
+				act_english_1q,
+				act_english_3q,
+				act_math_1q,
+				act_math_3q,
+				application_fee,
+				price_in_district,
+				price_in_state,
+				price_out_of_state
+			) VALUES (
+				$1, $2, $3, $4, $5, $6, $7, $8, $9, $10,
+				$11, $12, $13, $14, $15, $16, $17, $18, $19, $20,
+				$21, $22, $23, $24, $25, $26
+			) ON CONFLICT(university_id, year) DO UPDATE SET (
+				total_enrollment,
+				undergrad_enrollment,
+				student_to_faculty,
+				graduation_rate,
+				total_applicants,
+				total_admissions,
+				total_enrolled_applicants,
+				admissions_yield,
+				submitted_sat,
+				submitted_act,
+				sat_english_1q,
+				sat_english_3q,
+				sat_math_1q,
+				sat_math_3q,
+				act_composite_1q,
+				act_composite_3q,
+				act_english_1q,
+				act_english_3q,
+				act_math_1q,
+				act_math_3q,
+				application_fee,
+				price_in_district,
+				price_in_state,
+				price_out_of_state
+			) = (
+				excluded.total_enrollment,
+				excluded.undergrad_enrollment,
+				excluded.student_to_faculty,
+				excluded.graduation_rate,
+				excluded.total_applicants,
+				excluded.total_admissions,
+				excluded.total_enrolled_applicants,
+				excluded.admissions_yield,
+				excluded.submitted_sat,
+				excluded.submitted_act,
+				excluded.sat_english_1q,
+				excluded.sat_english_3q,
+				excluded.sat_math_1q,
+				excluded.sat_math_3q,
+				excluded.act_composite_1q,
+				excluded.act_composite_3q,
+				excluded.act_english_1q,
+				excluded.act_english_3q,
+				excluded.act_math_1q,
+				excluded.act_math_3q,
+				excluded.application_fee,
+				excluded.price_in_district,
+				excluded.price_in_state,
+				excluded.price_out_of_state
+			)",
+			self.university_id,
+			self.year,
+			self.total_enrollment,
+			self.undergrad_enrollment,
+			self.student_to_faculty,
+			self.graduation_rate,
+			self.total_applicants,
+			self.total_admissions,
+			self.total_enrolled_applicants,
+			self.admissions_yield,
+			self.submitted_sat,
+			self.submitted_act,
+			self.sat_english_1q,
+			self.sat_english_3q,
+			self.sat_math_1q,
+			self.sat_math_3q,
+			self.act_composite_1q,
+			self.act_composite_3q,
+			self.act_english_1q,
+			self.act_english_3q,
+			self.act_math_1q,
+			self.act_math_3q,
+			self.application_fee,
+			self.price_in_district,
+			self.price_in_state,
+			self.price_out_of_state
+		)
+		.execute(con)
+		.await
+		.map(|_| ())
+	}
+
+	/// every year of statistics, oldest first
+	pub async fn load_all(con: impl Executor<'_>, university_id: i64) -> sqlx::Result<Vec<Self>> {
+		sqlx::query_as!(
+			Self,
+			"SELECT * FROM university_stats WHERE university_id = $1 ORDER BY year",
+			university_id
+		)
+		.fetch_all(con)
+		.await
+	}
+}
+
+/// the 25th to 75th percentile of a test score
+#[derive(Debug, Serialize)]
+pub struct Range {
+	pub low: i64,
+	pub high: i64,
+}
+
+impl Range {
+	pub fn new(low: Option<i64>, high: Option<i64>) -> Option<Self> {
+		Some(Self {
+			low: low?,
+			high: high?,
+		})
+	}
+}
+
+/// how selective and expensive a university was in a year
+#[derive(Debug, Serialize)]
+pub struct Trend {
+	pub year: i64,
+	/// percent of applicants who were admitted
+	pub acceptance_rate: Option<f64>,
+	/// percent of admitted students who enrolled
+	pub admissions_yield: Option<i64>,
+	pub price_in_district: Option<i64>,
+	pub price_in_state: Option<i64>,
+	pub price_out_of_state: Option<i64>,
+	pub sat_english: Option<Range>,
+	pub sat_math: Option<Range>,
+	/// the sum of the English and math ranges
+	pub sat_total: Option<Range>,
+	pub act_composite: Option<Range>,
+}
+
+impl From<&Stats> for Trend {
+	fn from(stats: &Stats) -> Self {
+		let sat_english = Range::new(stats.sat_english_1q, stats.sat_english_3q);
+		let sat_math = Range::new(stats.sat_math_1q, stats.sat_math_3q);
+		let sat_total = sat_composite(sat_english.as_ref(), sat_math.as_ref());
+
+		Self {
+			year: stats.year,
+			acceptance_rate: percent(stats.total_admissions, stats.total_applicants),
+			admissions_yield: stats.admissions_yield,
+			price_in_district: stats.price_in_district,
+			price_in_state: stats.price_in_state,
+			price_out_of_state: stats.price_out_of_state,
+			sat_english,
+			sat_math,
+			sat_total,
+			act_composite: Range::new(stats.act_composite_1q, stats.act_composite_3q),
+		}
+	}
+}
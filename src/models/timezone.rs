@@ -0,0 +1,69 @@
+use std::{fmt, ops::Deref};
+
+use chrono_tz::Tz;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use sqlx::{
+	database::{HasArguments, HasValueRef},
+	encode::IsNull,
+	error::BoxDynError,
+	Database, Decode, Encode, Type,
+};
+
+use crate::db::DB;
+
+/// an IANA timezone, stored and serialized by name (e.g., `America/New_York`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timezone(pub Tz);
+
+impl Deref for Timezone {
+	type Target = Tz;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl fmt::Display for Timezone {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.0.name())
+	}
+}
+
+impl Serialize for Timezone {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(self.0.name())
+	}
+}
+
+impl<'de> Deserialize<'de> for Timezone {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let name = String::deserialize(deserializer)?;
+		name.parse().map(Self).map_err(de::Error::custom)
+	}
+}
+
+impl Type<DB> for Timezone {
+	fn type_info() -> <DB as Database>::TypeInfo {
+		<&str as Type<DB>>::type_info()
+	}
+
+	fn compatible(ty: &<DB as Database>::TypeInfo) -> bool {
+		<&str as Type<DB>>::compatible(ty)
+	}
+}
+
+impl<'r> Decode<'r, DB> for Timezone {
+	fn decode(value: <DB as HasValueRef<'r>>::ValueRef) -> Result<Self, BoxDynError> {
+		let name = <&str as Decode<DB>>::decode(value)?;
+		name.parse()
+			.map(Self)
+			.map_err(|_| format!("Could not load {} as timezone", name).into())
+	}
+}
+
+impl<'q> Encode<'q, DB> for Timezone {
+	fn encode_by_ref(&self, buf: &mut <DB as HasArguments<'q>>::ArgumentBuffer) -> IsNull {
+		// `Tz::name` is `'static`, so it can be borrowed for as long as the query needs it
+		<&str as Encode<DB>>::encode(self.0.name(), buf)
+	}
+}
@@ -0,0 +1,299 @@
+//! what a user thinks of each university they're tracking, and how they've sorted them
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::{Acquire, Executor, Pool};
+
+use super::{checklist::ChecklistItem, list::List, university::University};
+
+/// the longest a tag can be, in characters
+const MAX_TAG_LENGTH: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+pub enum Category {
+	Reach,
+	Match,
+	Safety,
+	Visited,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct Tracking {
+	pub notes: Option<String>,
+	/// 1 to 5 stars
+	pub rating: Option<i64>,
+	pub category: Option<Category>,
+	/// where the user has sorted it, lowest first
+	pub position: i64,
+	pub tags: Vec<String>,
+}
+
+/// the fields a user can edit, which replace the old ones
+#[derive(Debug, Deserialize)]
+pub struct TrackingData {
+	pub notes: Option<String>,
+	pub rating: Option<i64>,
+	pub category: Option<Category>,
+	#[serde(default)]
+	pub tags: Vec<String>,
+}
+
+impl TrackingData {
+	/// checks the rating and tags, trimming and deduplicating the tags
+	pub fn validate(&mut self) -> Result<(), &'static str> {
+		if !self.rating.is_none_or(|rating| (1..=5).contains(&rating)) {
+			return Err("rating must be between 1 and 5");
+		}
+
+		let mut tags = Vec::with_capacity(self.tags.len());
+		for tag in &self.tags {
+			let tag = tag.trim();
+			if tag.is_empty() {
+				return Err("tags cannot be empty");
+			} else if tag.chars().count() > MAX_TAG_LENGTH {
+				return Err("tags must be at most 32 characters");
+			}
+
+			if !tags.iter().any(|t: &String| t == tag) {
+				tags.push(tag.to_owned());
+			}
+		}
+		self.tags = tags;
+
+		Ok(())
+	}
+}
+
+/// only the tracked universities matching every field that's set
+#[derive(Debug, Default, Deserialize)]
+pub struct TrackingFilter {
+	pub tag: Option<String>,
+	pub category: Option<Category>,
+	pub min_rating: Option<i64>,
+}
+
+impl TrackingFilter {
+	pub fn matches(&self, tracking: &Tracking) -> bool {
+		self.tag
+			.as_ref()
+			.is_none_or(|tag| tracking.tags.contains(tag))
+			&& self
+				.category
+				.is_none_or(|category| tracking.category == Some(category))
+			&& self
+				.min_rating
+				.is_none_or(|min| tracking.rating.is_some_and(|rating| rating >= min))
+	}
+}
+
+#[derive(Debug, Serialize)]
+pub struct TagCount {
+	pub tag: String,
+	pub count: i64,
+}
+
+impl Tracking {
+	/// the user's notes, ratings and tags, by university id
+	pub async fn load_all(
+		con: impl Executor<'_> + Clone,
+		user_id: Uuid,
+	) -> sqlx::Result<HashMap<i64, Self>> {
+		let mut tracking: HashMap<_, _> = sqlx::query!(
+			r#"SELECT
+				university_id,
+				notes,
+				rating,
+				category AS "category: Category",
+				position
+			FROM get_weather WHERE user_id = $1"#,
+			user_id
+		)
+		.fetch_all(con.clone())
+		.await?
+		.into_iter()
+		.map(|row| {
+			(
+				row.university_id,
+				Self {
+					notes: row.notes,
+					rating: row.rating,
+					category: row.category,
+					position: row.position,
+					tags: Vec::new(),
+				},
+			)
+		})
+		.collect();
+
+		let tags = sqlx::query!(
+			"SELECT university_id, tag FROM tracked_tags WHERE user_id = $1 ORDER BY tag",
+			user_id
+		)
+		.fetch_all(con)
+		.await?;
+
+		for row in tags {
+			if let Some(tracking) = tracking.get_mut(&row.university_id) {
+				tracking.tags.push(row.tag);
+			}
+		}
+
+		Ok(tracking)
+	}
+
+	/// starts tracking a university, at the end of the user's list, if they aren't already
+	pub async fn track(
+		con: impl Acquire<'_>,
+		user_id: Uuid,
+		university_id: i64,
+	) -> sqlx::Result<()> {
+		let mut trans = con.begin().await?;
+
+		sqlx::query!(
+			"INSERT INTO get_weather (university_id, user_id, position)
+			VALUES ($1, $2, (SELECT coalesce(MAX(position) + 1, 0) FROM get_weather WHERE user_id = $2))
+			ON CONFLICT (university_id, user_id) DO NOTHING",
+			university_id,
+			user_id
+		)
+		.execute(&mut trans)
+		.await?;
+
+		// the default list is the tracked universities
+		let list_id = List::default_id(&mut trans, user_id).await?;
+		List::add(&mut trans, list_id, university_id).await?;
+
+		if let Some(university) = University::load(&mut trans, university_id).await? {
+			ChecklistItem::generate(&mut trans, user_id, &university).await?;
+		}
+
+		trans.commit().await
+	}
+
+	pub async fn untrack(con: &Pool, user_id: Uuid, university_id: i64) -> sqlx::Result<()> {
+		let mut trans = con.begin().await?;
+
+		sqlx::query!(
+			"DELETE FROM get_weather WHERE (university_id, user_id) = ($1, $2)",
+			university_id,
+			user_id
+		)
+		.execute(&mut trans)
+		.await?;
+
+		let list_id = List::default_id(&mut trans, user_id).await?;
+		List::remove(&mut trans, list_id, university_id).await?;
+
+		trans.commit().await
+	}
+
+	/// replaces the user's notes, rating, category and tags for a university,
+	/// returning whether they're tracking it
+	pub async fn put(
+		con: impl Acquire<'_>,
+		user_id: Uuid,
+		university_id: i64,
+		data: &TrackingData,
+	) -> sqlx::Result<bool> {
+		let mut trans = con.begin().await?;
+
+		let updated = sqlx::query!(
+			"UPDATE get_weather SET (notes, rating, category) = ($1, $2, $3)
+			WHERE university_id = $4 AND user_id = $5",
+			data.notes,
+			data.rating,
+			data.category,
+			university_id,
+			user_id
+		)
+		.execute(&mut trans)
+		.await?
+		.rows_affected()
+			> 0;
+
+		if !updated {
+			return Ok(false);
+		}
+
+		sqlx::query!(
+			"DELETE FROM tracked_tags WHERE university_id = $1 AND user_id = $2",
+			university_id,
+			user_id
+		)
+		.execute(&mut trans)
+		.await?;
+
+		for tag in &data.tags {
+			sqlx::query!(
+				"INSERT INTO tracked_tags (university_id, user_id, tag) VALUES ($1, $2, $3)",
+				university_id,
+				user_id,
+				tag
+			)
+			.execute(&mut trans)
+			.await?;
+		}
+
+		trans.commit().await.map(|_| true)
+	}
+
+	/// sorts the user's universities (and their default list) in the order of `ids`,
+	/// which must be all of them
+	pub async fn reorder(con: &Pool, user_id: Uuid, ids: &[i64]) -> sqlx::Result<()> {
+		let mut trans = con.begin().await?;
+		let list_id = List::default_id(&mut trans, user_id).await?;
+
+		for (position, id) in ids.iter().enumerate() {
+			let position = position as i64;
+			sqlx::query!(
+				"UPDATE get_weather SET position = $1 WHERE university_id = $2 AND user_id = $3",
+				position,
+				id,
+				user_id
+			)
+			.execute(&mut trans)
+			.await?;
+
+			let updated = sqlx::query!(
+				"UPDATE list_entries SET position = $1 WHERE list_id = $2 AND university_id = $3",
+				position,
+				list_id,
+				id
+			)
+			.execute(&mut trans)
+			.await?
+			.rows_affected();
+
+			// tracking and the default list are always changed together, so this is a bug
+			if updated == 0 {
+				log::error!(
+					"University {} is tracked by {} but not in their default list",
+					id,
+					user_id
+				);
+				return Err(sqlx::Error::RowNotFound);
+			}
+		}
+
+		trans.commit().await
+	}
+
+	/// every tag the user has used, with how many universities have it
+	pub async fn tags(con: impl Executor<'_>, user_id: Uuid) -> sqlx::Result<Vec<TagCount>> {
+		sqlx::query_as!(
+			TagCount,
+			r#"SELECT tag, COUNT(*) AS "count!: i64" FROM tracked_tags
+			WHERE user_id = $1
+			GROUP BY tag
+			ORDER BY tag"#,
+			user_id
+		)
+		.fetch_all(con)
+		.await
+	}
+}
@@ -1,22 +1,29 @@
 use openweather_api::Coordinates;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 use crate::db::Executor;
 
-// #[derive(Debug, Type)]
-// #[repr(u8)]
-// pub enum Consideration {
-// 	Unspecified = 0,
-// 	Required = 1,
-// 	Recommended = 2,
-// 	Considered = 3,
-// 	NotRecommended = 4,
-// }
-pub type Consideration = i32;
+use super::timezone::Timezone;
+
+/// how much an admissions factor matters, as reported to IPEDS
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[repr(i32)]
+pub enum Consideration {
+	Unspecified = 0,
+	Required = 1,
+	Recommended = 2,
+	Considered = 3,
+	NotRecommended = 4,
+}
 
 #[derive(Debug, Serialize)]
 pub struct University {
 	pub id: i64,
+	/// IPEDS unit id
+	pub unitid: Option<i64>,
+	/// Office of Postsecondary Education id
+	pub opeid: Option<String>,
 	pub name: String,
 	pub aliases: Option<String>,
 	pub street_address: String,
@@ -58,14 +65,80 @@ pub struct University {
 	pub price_in_district: Option<i64>,
 	pub price_in_state: Option<i64>,
 	pub price_out_of_state: Option<i64>,
-	pub timezone: String,
+	pub timezone: Timezone,
+	/// the year the statistics are from, if there are any
+	pub stats_year: Option<i64>,
 }
 
 impl University {
 	pub async fn load(con: impl Executor<'_>, id: i64) -> sqlx::Result<Option<Self>> {
-		sqlx::query_as!(Self, "SELECT * FROM universities WHERE id = $1", id)
+		sqlx::query_as!(
+			Self,
+			r#"SELECT
+				u.id,
+				u.unitid,
+				u.opeid,
+				u.name,
+				u.aliases,
+				u.street_address,
+				u.city,
+				u.state,
+				u.zip_code,
+				u.website,
+				u.admissions_website,
+				u.longitude,
+				u.latitude,
+				s.total_enrollment,
+				s.undergrad_enrollment,
+				s.student_to_faculty,
+				s.graduation_rate,
+				u.open_admission,
+				u.considers_gpa AS "considers_gpa: Consideration",
+				u.considers_class_rank AS "considers_class_rank: Consideration",
+				u.considers_transcript AS "considers_transcript: Consideration",
+				u.considers_recommendations AS "considers_recommendations: Consideration",
+				u.considers_test_scores AS "considers_test_scores: Consideration",
+				u.considers_toefl AS "considers_toefl: Consideration",
+				s.total_applicants,
+				s.total_admissions,
+				s.total_enrolled_applicants,
+				s.admissions_yield,
+				s.submitted_sat,
+				s.submitted_act,
+				s.sat_english_1q,
+				s.sat_english_3q,
+				s.sat_math_1q,
+				s.sat_math_3q,
+				s.act_composite_1q,
+				s.act_composite_3q,
+				s.act_english_1q,
+				s.act_english_3q,
+				s.act_math_1q,
+				s.act_math_3q,
+				s.application_fee,
+				s.price_in_district,
+				s.price_in_state,
+				s.price_out_of_state,
+				u.timezone AS "timezone: Timezone",
+				s.year AS "stats_year?"
+			FROM universities AS u
+			-- the most recent year of statistics
+			LEFT JOIN university_stats AS s ON s.university_id = u.id AND s.year = (
+				SELECT MAX(year) FROM university_stats WHERE university_id = u.id
+			)
+			WHERE u.id = $1"#,
+			id
+		)
+		.fetch_optional(con)
+		.await
+	}
+
+	/// gets the id of the university with an IPEDS unit id
+	pub async fn id_from_unitid(con: impl Executor<'_>, unitid: i64) -> sqlx::Result<Option<i64>> {
+		sqlx::query!("SELECT id FROM universities WHERE unitid = $1", unitid)
 			.fetch_optional(con)
 			.await
+			.map(|row| row.map(|row| row.id))
 	}
 
 	pub async fn get_coordinates(
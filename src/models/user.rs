@@ -3,9 +3,9 @@ use futures_util::future;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::db::Executor;
+use crate::db::{Acquire, Executor};
 
-use super::university::University;
+use super::{home::Home, list::List, timezone::Timezone, university::University};
 
 #[derive(Debug, Serialize)]
 pub struct User {
@@ -14,54 +14,53 @@ pub struct User {
 	pub universities: Vec<i64>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, sqlx::Type)]
 #[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
 pub enum Units {
 	Imperial,
 	Metric,
 }
 
-impl Units {
-	#[inline]
-	pub const fn as_str(&self) -> &'static str {
-		match &self {
-			Self::Imperial => "imperial",
-			Self::Metric => "metric",
-		}
-	}
-}
-
-impl<'a> TryFrom<&'a str> for Units {
-	type Error = ();
-
-	fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-		match value {
-			"imperial" => Ok(Self::Imperial),
-			"metric" => Ok(Self::Metric),
-			_ => Err(()),
-		}
-	}
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Metadata {
 	pub username: String,
 	pub units: Units,
-	pub timezone: Option<i64>,
+	pub timezone: Option<Timezone>,
+	#[serde(default)]
+	pub home: Option<Home>,
 }
 
 impl Metadata {
 	pub async fn load(con: impl Executor<'_>, id: Uuid) -> sqlx::Result<Option<Self>> {
-		sqlx::query!("SELECT * FROM users WHERE id = $1", id)
-			.fetch_optional(con)
-			.await
-			.map(|opt| {
-				opt.map(|row| Self {
-					username: row.username,
-					units: row.units.as_str().try_into().unwrap(),
-					timezone: row.timezone,
-				})
-			})
+		let row = sqlx::query!(
+			r#"SELECT
+				username,
+				units AS "units: Units",
+				timezone AS "timezone: Timezone",
+				home_latitude,
+				home_longitude,
+				home_zip
+			FROM users WHERE id = $1"#,
+			id
+		)
+		.fetch_optional(con)
+		.await?;
+
+		Ok(row.map(|row| {
+			let home = (row.home_latitude.is_some() || row.home_zip.is_some()).then_some(Home {
+				latitude: row.home_latitude,
+				longitude: row.home_longitude,
+				zip: row.home_zip,
+			});
+
+			Self {
+				username: row.username,
+				units: row.units,
+				timezone: row.timezone,
+				home,
+			}
+		}))
 	}
 }
 
@@ -71,7 +70,7 @@ impl User {
 
 		if let Some(metadata) = metadata {
 			let universities = sqlx::query!(
-				"SELECT university_id FROM get_weather WHERE user_id = $1",
+				"SELECT university_id FROM get_weather WHERE user_id = $1 ORDER BY position, university_id",
 				id
 			)
 			.fetch_all(con)
@@ -92,20 +91,35 @@ impl User {
 }
 
 impl User {
-	pub async fn create(con: impl Executor<'_> + Clone, metadata: Metadata) -> sqlx::Result<Self> {
+	/// creates the user and their default list
+	pub async fn create(con: impl Acquire<'_>, metadata: Metadata) -> sqlx::Result<Self> {
+		let mut trans = con.begin().await?;
 		let id = Uuid::new_v4();
+		let home = metadata.home.as_ref();
+		let (latitude, longitude, zip) = (
+			home.and_then(|home| home.latitude),
+			home.and_then(|home| home.longitude),
+			home.and_then(|home| home.zip.as_deref()),
+		);
 
-		let units = metadata.units.as_str();
 		sqlx::query!(
-			"INSERT INTO users (id, username, units, timezone) VALUES ($1, $2, $3, $4)",
+			"INSERT INTO users (id, username, units, timezone, home_latitude, home_longitude, home_zip)
+			VALUES ($1, $2, $3, $4, $5, $6, $7)",
 			id,
 			metadata.username,
-			units,
+			metadata.units,
 			metadata.timezone,
+			latitude,
+			longitude,
+			zip,
 		)
-		.execute(con)
-		.await
-		.map(|_| Self {
+		.execute(&mut trans)
+		.await?;
+
+		List::create_default(&mut trans, id).await?;
+		trans.commit().await?;
+
+		Ok(Self {
 			id,
 			metadata,
 			universities: vec![],
@@ -113,12 +127,22 @@ impl User {
 	}
 
 	pub async fn update(&self, con: impl Executor<'_>) -> sqlx::Result<()> {
-		let units = self.metadata.units.as_str();
+		let home = self.metadata.home.as_ref();
+		let (latitude, longitude, zip) = (
+			home.and_then(|home| home.latitude),
+			home.and_then(|home| home.longitude),
+			home.and_then(|home| home.zip.as_deref()),
+		);
+
 		sqlx::query!(
-			"UPDATE users SET (username, units, timezone) = ($1, $2, $3) WHERE id = $4",
+			"UPDATE users SET (username, units, timezone, home_latitude, home_longitude, home_zip)
+			= ($1, $2, $3, $4, $5, $6) WHERE id = $7",
 			self.metadata.username,
-			units,
+			self.metadata.units,
 			self.metadata.timezone,
+			latitude,
+			longitude,
+			zip,
 			self.id
 		)
 		.execute(con)
@@ -1,15 +1,20 @@
 use crate::db::{Executor, Pool};
-use chrono::{naive::serde::ts_milliseconds, NaiveDateTime, Utc};
+use chrono::{serde::ts_milliseconds, DateTime, Duration, Utc};
 use openweather_api::{Client, Coordinates};
 use serde::Serialize;
 
 use super::university::University;
 
+/// thunderstorm (2xx), drizzle (3xx), rain (5xx) and snow (6xx) are precipitation
+pub fn is_precipitation(weather_id: i64) -> bool {
+	matches!(weather_id / 100, 2 | 3 | 5 | 6)
+}
+
 #[derive(Debug, Serialize)]
 pub struct Weather {
 	pub university_id: i64,
 	#[serde(with = "ts_milliseconds")]
-	pub time: NaiveDateTime,
+	pub time: DateTime<Utc>,
 	pub temperature: f64,
 	pub feels_like: f64,
 	pub weather_type: i64,
@@ -31,7 +36,7 @@ impl Weather {
 			let main_weather = weather.weather.into_iter().next().unwrap();
 			Self {
 				university_id,
-				time: Utc::now().naive_utc(),
+				time: Utc::now(),
 				temperature: weather.main.temp.0,
 				feels_like: weather.main.feels_like.0,
 				// TODO: check if weather.len() > 0?
@@ -63,7 +68,18 @@ impl Weather {
 	pub async fn get_all(con: &Pool, university_id: i64) -> sqlx::Result<Vec<Self>> {
 		sqlx::query_as!(
 			Self,
-			"SELECT * FROM weather WHERE university_id = $1",
+			r#"SELECT
+				university_id,
+				time AS "time: DateTime<Utc>",
+				temperature,
+				feels_like,
+				weather_type,
+				weather_description,
+				humidity,
+				pressure,
+				wind_speed,
+				cloudiness
+			FROM weather WHERE university_id = $1"#,
 			university_id
 		)
 		.fetch_all(con)
@@ -77,10 +93,21 @@ impl Weather {
 	) -> sqlx::Result<Vec<Self>> {
 		sqlx::query_as!(
 			Self,
-			"SELECT * FROM weather
+			r#"SELECT
+				university_id,
+				time AS "time: DateTime<Utc>",
+				temperature,
+				feels_like,
+				weather_type,
+				weather_description,
+				humidity,
+				pressure,
+				wind_speed,
+				cloudiness
+			FROM weather
 			WHERE university_id = $1
 			ORDER BY time DESC
-			LIMIT $2",
+			LIMIT $2"#,
 			university_id,
 			limit
 		)
@@ -88,7 +115,56 @@ impl Weather {
 		.await
 	}
 
+	pub async fn get_between(
+		con: impl Executor<'_>,
+		university_id: i64,
+		start: DateTime<Utc>,
+		end: DateTime<Utc>,
+	) -> sqlx::Result<Vec<Self>> {
+		let (start, end) = (start.naive_utc(), end.naive_utc());
+		sqlx::query_as!(
+			Self,
+			r#"SELECT
+				university_id,
+				time AS "time: DateTime<Utc>",
+				temperature,
+				feels_like,
+				weather_type,
+				weather_description,
+				humidity,
+				pressure,
+				wind_speed,
+				cloudiness
+			FROM weather
+			WHERE university_id = $1 AND time BETWEEN $2 AND $3
+			ORDER BY time"#,
+			university_id,
+			start,
+			end
+		)
+		.fetch_all(con)
+		.await
+	}
+
+	/// gets the observation closest to `time`, if there is one within `max_distance`
+	pub async fn get_closest(
+		con: impl Executor<'_>,
+		university_id: i64,
+		time: DateTime<Utc>,
+		max_distance: Duration,
+	) -> sqlx::Result<Option<Self>> {
+		Self::get_between(con, university_id, time - max_distance, time + max_distance)
+			.await
+			.map(|observations| {
+				observations
+					.into_iter()
+					.min_by_key(|obs| (obs.time - time).num_seconds().abs())
+			})
+	}
+
 	pub async fn put(&self, con: impl Executor<'_>) -> sqlx::Result<()> {
+		// timestamps are stored as naive UTC
+		let time = self.time.naive_utc();
 		sqlx::query!(
 			"INSERT INTO weather (
 				university_id,
@@ -103,7 +179,7 @@ impl Weather {
 				cloudiness
 		) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
 			self.university_id,
-			self.time,
+			time,
 			self.temperature,
 			self.feels_like,
 			self.weather_type,
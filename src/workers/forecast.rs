@@ -4,7 +4,10 @@ use anyhow::Context;
 use futures_util::future::BoxFuture;
 use openweather_api::{Client, Coordinates};
 
-use crate::{db::Pool, models::forecast::Forecast};
+use crate::{
+	db::Pool,
+	models::{forecast::Forecast, location::Location},
+};
 
 use super::Update;
 
@@ -26,14 +29,31 @@ impl Update for ForecastUpdater {
 
 impl ForecastUpdater {
 	async fn run(self) -> anyhow::Result<()> {
+		// neither universities nor locations failing should stop the other from updating
+		let universities = self.update_universities().await;
+		let locations = self.update_locations().await;
+
+		match (universities, locations) {
+			(Err(universities), Err(locations)) => {
+				log::error!("Error updating locations: {:?}", locations);
+				Err(universities)
+			}
+			(universities, locations) => universities.and(locations),
+		}
+	}
+
+	async fn update_universities(&self) -> anyhow::Result<()> {
 		log::info!("Getting universities..");
 		let universities = sqlx::query!(
-			r#"SELECT DISTINCT
-					get_weather.university_id,
+			r#"SELECT
+					universities.id AS university_id,
 					universities.longitude,
 					universities.latitude
-				FROM get_weather INNER JOIN universities
-					ON get_weather.university_id = universities.id"#
+				FROM universities
+				WHERE universities.id IN (
+					SELECT university_id FROM get_weather
+					UNION SELECT university_id FROM list_entries
+				)"#
 		)
 		.fetch_all(&self.con)
 		.await
@@ -76,4 +96,52 @@ impl ForecastUpdater {
 		log::info!("Successfully committed transaction.");
 		Ok(())
 	}
+
+	/// users' locations are updated in their own transaction,
+	/// and a location that can't be fetched is skipped instead of holding back the others
+	async fn update_locations(&self) -> anyhow::Result<()> {
+		let locations = Location::load_every(&self.con)
+			.await
+			.context("Error getting locations to fetch from database.")?;
+
+		let mut trans = self
+			.con
+			.begin()
+			.await
+			.context("Error beginning transaction")?;
+
+		for location in locations {
+			let forecasts = match Forecast::fetch(
+				self.client.clone(),
+				location.id,
+				&location.coordinates(),
+				40,
+			)
+			.await
+			{
+				Ok(forecasts) => forecasts,
+				Err(err) => {
+					log::warn!(
+						"Error fetching from openweather API (location {}: ({:.3}, {:.3})): {}",
+						location.id,
+						location.latitude,
+						location.longitude,
+						err
+					);
+					continue;
+				}
+			};
+
+			for forecast in forecasts {
+				Location::put_forecast(&mut trans, &forecast)
+					.await
+					.with_context(|| {
+						format!("Error inserting forecast {:?} into database", forecast)
+					})?;
+			}
+			log::info!("Updated location {}", location.id);
+		}
+
+		trans.commit().await.context("Error committing transaction")
+	}
 }
@@ -6,6 +6,7 @@ use chrono::Utc;
 use futures_util::Future;
 
 pub mod forecast;
+pub mod reminders;
 pub mod weather;
 
 pub trait Update: Debug + Clone + Unpin + 'static {
@@ -0,0 +1,125 @@
+use std::{fmt::Debug, sync::Arc, time::Duration};
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use futures_util::future::BoxFuture;
+use uuid::Uuid;
+
+use crate::{
+	db::Pool,
+	models::deadline::{due_at, Deadline, PendingReminder},
+};
+
+use super::Update;
+
+/// how many days before a deadline each reminder is sent, furthest first
+const THRESHOLDS: [i64; 2] = [7, 1];
+
+/// a deadline that is coming up soon
+#[derive(Debug)]
+pub struct Reminder {
+	pub user_id: Uuid,
+	pub username: String,
+	pub university_name: String,
+	pub deadline: Deadline,
+	pub due_at: DateTime<Utc>,
+}
+
+/// somewhere to send reminders (e.g., email or push notifications)
+pub trait Notifier: Debug + Send + Sync {
+	fn notify<'a>(&'a self, reminder: &'a Reminder) -> BoxFuture<'a, anyhow::Result<()>>;
+}
+
+/// writes reminders to the log, until there's a better way to send them
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogNotifier;
+
+impl Notifier for LogNotifier {
+	fn notify<'a>(&'a self, reminder: &'a Reminder) -> BoxFuture<'a, anyhow::Result<()>> {
+		Box::pin(async move {
+			log::info!(
+				"Reminder for {} ({}): {:?} deadline at {} is due {}",
+				reminder.username,
+				reminder.user_id,
+				reminder.deadline.kind,
+				reminder.university_name,
+				reminder.due_at
+			);
+			Ok(())
+		})
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct ReminderUpdater {
+	pub con: Pool,
+	pub notifier: Arc<dyn Notifier>,
+}
+
+impl Update for ReminderUpdater {
+	const INTERVAL_LENGTH: Duration = Duration::from_secs(60 * 60);
+
+	type Future = BoxFuture<'static, anyhow::Result<()>>;
+
+	fn update(self) -> Self::Future {
+		Box::pin(self.run())
+	}
+}
+
+impl ReminderUpdater {
+	async fn run(self) -> anyhow::Result<()> {
+		let now = Utc::now();
+		let today = now.date().naive_utc();
+
+		// a day of slack on either side, since deadlines are in the university's timezone
+		let pending = PendingReminder::load(
+			&self.con,
+			today.pred(),
+			today + chrono::Duration::days(THRESHOLDS[0] + 1),
+			THRESHOLDS.len() as i64,
+		)
+		.await
+		.context("Error getting upcoming deadlines from database.")?;
+
+		for pending in pending {
+			let due_at = due_at(pending.deadline.due, &pending.timezone);
+			let remaining = due_at - now;
+
+			if remaining < chrono::Duration::zero() {
+				continue;
+			}
+
+			// only the latest reminder is sent if several are due at once
+			let reached = THRESHOLDS
+				.iter()
+				.filter(|&&days| remaining <= chrono::Duration::days(days))
+				.count() as i64;
+
+			if reached <= pending.reminders_sent {
+				continue;
+			}
+
+			let deadline_id = pending.deadline.id;
+			let reminder = Reminder {
+				user_id: pending.user_id,
+				username: pending.username,
+				university_name: pending.university_name,
+				deadline: pending.deadline,
+				due_at,
+			};
+
+			// one reminder failing shouldn't hold back the rest. it isn't marked sent,
+			// so it's tried again next hour
+			if let Err(err) = self.notifier.notify(&reminder).await {
+				log::warn!("Error sending reminder {:?}: {:#}", reminder, err);
+				continue;
+			}
+
+			PendingReminder::mark_sent(&self.con, deadline_id, reached)
+				.await
+				.context("Error marking reminder as sent")?;
+		}
+
+		Ok(())
+	}
+}
@@ -3,7 +3,10 @@ use std::time::Duration;
 use anyhow::Context as _;
 use openweather_api::{Client, Coordinates};
 
-use crate::{db::Pool, models::weather::Weather};
+use crate::{
+	db::Pool,
+	models::{location::Location, weather::Weather},
+};
 
 use super::Update;
 
@@ -25,14 +28,31 @@ impl Update for WeatherUpdater {
 
 impl WeatherUpdater {
 	async fn run(self) -> anyhow::Result<()> {
+		// neither universities nor locations failing should stop the other from updating
+		let universities = self.update_universities().await;
+		let locations = self.update_locations().await;
+
+		match (universities, locations) {
+			(Err(universities), Err(locations)) => {
+				log::error!("Error updating locations: {:?}", locations);
+				Err(universities)
+			}
+			(universities, locations) => universities.and(locations),
+		}
+	}
+
+	async fn update_universities(&self) -> anyhow::Result<()> {
 		log::info!("Getting universities..");
 		let universities = sqlx::query!(
-			r#"SELECT DISTINCT
-								get_weather.university_id,
+			r#"SELECT
+								universities.id AS university_id,
 								universities.longitude,
 								universities.latitude
-							FROM get_weather INNER JOIN universities
-								ON get_weather.university_id = universities.id"#
+							FROM universities
+							WHERE universities.id IN (
+								SELECT university_id FROM get_weather
+								UNION SELECT university_id FROM list_entries
+							)"#
 		)
 		.fetch_all(&self.con)
 		.await
@@ -76,4 +96,43 @@ impl WeatherUpdater {
 		log::info!("Committed transaction");
 		Ok(())
 	}
+
+	/// users' locations are updated in their own transaction,
+	/// and a location that can't be fetched is skipped instead of holding back the others
+	async fn update_locations(&self) -> anyhow::Result<()> {
+		let locations = Location::load_every(&self.con)
+			.await
+			.context("Error getting locations to fetch from database.")?;
+
+		let mut trans = self
+			.con
+			.begin()
+			.await
+			.context("Error beginning transaction.")?;
+
+		for location in locations {
+			let weather =
+				match Weather::fetch(&self.client, location.id, &location.coordinates()).await {
+					Ok(weather) => weather,
+					Err(err) => {
+						log::warn!(
+							"Error fetching from openweather API (location {}: ({:.3}, {:.3})): {}",
+							location.id,
+							location.latitude,
+							location.longitude,
+							err
+						);
+						continue;
+					}
+				};
+
+			Location::put_weather(&mut trans, &weather)
+				.await
+				.with_context(|| format!("Error inserting weather {:?} into database", weather))?;
+
+			log::info!("Updated location {}", location.id);
+		}
+
+		trans.commit().await.context("Error committing transaction")
+	}
 }
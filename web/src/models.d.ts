@@ -1,14 +1,16 @@
-// enum Consideration {
-//   Unspecified,
-//   Required,
-//   Recommended,
-//   Considered,
-//   NotRecommended,
-// }
-export type Consideration = 0 | 1 | 2 | 3 | 4;
+export type Consideration =
+  | "unspecified"
+  | "required"
+  | "recommended"
+  | "considered"
+  | "not_recommended";
 
 export interface University {
   id: number;
+  /** IPEDS unit id */
+  unitid?: number;
+  /** Office of Postsecondary Education id */
+  opeid?: string;
   name: string;
   aliases?: string;
   street_address: string;
@@ -51,6 +53,164 @@ export interface University {
   price_in_state?: number;
   price_out_of_state?: number;
   timezone: string;
+  /** the year the statistics are from */
+  stats_year?: number;
+  admissions: AdmissionsSummary;
+}
+
+export interface Range {
+  low: number;
+  high: number;
+}
+
+/** computed by the server; missing values are null */
+export interface AdmissionsSummary {
+  acceptance_rate: number | null;
+  yield_rate: number | null;
+  sat_composite: Range | null;
+  act_composite: Range | null;
+  price_gap: number | null;
+}
+
+export type Fit = "reach" | "match" | "safety";
+
+/** how a tracked university fits the user's admissions profile */
+export interface Classification {
+  /** null without a profile or anything to compare */
+  fit: Fit | null;
+  compared: {
+    factor: "sat" | "act" | "act_english" | "act_math" | "gpa";
+    position: "below" | "within" | "above";
+  }[];
+}
+
+export type Category = "reach" | "match" | "safety" | "visited";
+
+export interface Tracking {
+  notes: string | null;
+  /** 1 to 5 stars */
+  rating: number | null;
+  category: Category | null;
+  /** where the user has sorted it, lowest first */
+  position: number;
+  tags: string[];
+}
+
+export interface TrackedUniversity extends University {
+  classification: Classification;
+  tracking: Tracking;
+}
+
+export interface AdmissionsProfile {
+  gpa: number | null;
+  sat_english: number | null;
+  sat_math: number | null;
+  act_composite: number | null;
+  act_english: number | null;
+  act_math: number | null;
+}
+
+export type DeadlineKind =
+  | "early_action"
+  | "early_decision"
+  | "regular"
+  | "financial_aid"
+  | "custom";
+
+export interface Deadline {
+  id: number;
+  university_id: number;
+  kind: DeadlineKind;
+  /** always set for custom deadlines */
+  name?: string;
+  /** YYYY-MM-DD, due by the end of the day at the university */
+  due: string;
+  notes?: string;
+}
+
+export type Requirement =
+  | "application"
+  | "application_fee"
+  | "transcript"
+  | "recommendations"
+  | "test_scores"
+  | "toefl";
+
+export interface ChecklistItem {
+  id: number;
+  university_id: number;
+  /** null for items the user added */
+  requirement: Requirement | null;
+  label: string;
+  done: boolean;
+}
+
+export interface UniversityProgress {
+  university_id: number;
+  name: string;
+  done: number;
+  total: number;
+  items: ChecklistItem[];
+}
+
+export interface Progress {
+  done: number;
+  total: number;
+  universities: UniversityProgress[];
+}
+
+export type Role = "viewer" | "editor" | "owner";
+
+export interface List {
+  id: number;
+  name: string;
+  /** mirrors the tracked universities, so it can't be edited directly */
+  is_default: boolean;
+  /** the current user's role */
+  role: Role;
+  /** university ids, in the list's order */
+  universities: number[];
+}
+
+export interface Member {
+  username: string;
+  role: Role;
+  /** false until they accept their invitation */
+  accepted: boolean;
+}
+
+export interface Invitation {
+  list_id: number;
+  name: string;
+  role: Role;
+  /** the owner's username */
+  owner: string;
+}
+
+export interface MemberNote {
+  university_id: number;
+  username: string;
+  notes: string;
+}
+
+export interface ShareLink {
+  token: string;
+  list_id: number;
+  created_at: number;
+  /** null if it never expires */
+  expires_at: number | null;
+  revoked: boolean;
+}
+
+export interface Location {
+  id: number;
+  name: string;
+  /** what OpenWeather calls the place */
+  geocoded_name: string | null;
+  latitude: number;
+  longitude: number;
+  /** IANA timezone name */
+  timezone: string;
 }
 
 export interface Weather {
@@ -75,7 +235,43 @@ export interface User {
 export interface UserMetadata {
   username: string;
   units: "imperial" | "metric";
-  timezone?: number;
+  /** IANA timezone name */
+  timezone?: string;
+  home?: Home | null;
+}
+
+/** set either latitude and longitude or zip; the coordinates are filled in from the zip */
+export interface Home {
+  latitude: number | null;
+  longitude: number | null;
+  zip: string | null;
+}
+
+export interface Conditions {
+  temperature: number;
+  feels_like: number;
+  weather_type: number;
+  weather_description: string;
+  humidity: number;
+  wind_speed: number;
+}
+
+export interface FromHome {
+  university_id: number;
+  name: string;
+  /** great-circle distance */
+  distance_km: number;
+  driving: "short" | "half_day" | "full_day" | "not_practical";
+  flying: "not_needed" | "short_haul" | "medium_haul" | "long_haul";
+  /** hours the university is ahead of home (negative if behind) */
+  time_difference_hours: number;
+  weather: Conditions | null;
+}
+
+export interface HomeComparison {
+  /** the weather at home */
+  weather: Conditions | null;
+  universities: FromHome[];
 }
 
 export interface JwtInfo {
@@ -97,3 +293,9 @@ export interface Forecast {
   precipitation_chance: number;
   is_day: boolean;
 }
+
+/** a location's weather, with `location_id` in place of `university_id` */
+export type LocationWeather = Omit<Weather, "university_id"> & { location_id: number };
+
+/** a location's forecast, with `location_id` in place of `university_id` */
+export type LocationForecast = Omit<Forecast, "university_id"> & { location_id: number };